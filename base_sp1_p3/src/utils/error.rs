@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+/// The errors of checking a batch of Poseidon2 permutations, and the FRI parameters to prove it
+/// with, before proving it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum P3ProveError {
+    #[error("{inputs} inputs but {outputs} outputs")]
+    LengthMismatch { inputs: usize, outputs: usize },
+
+    /// The claimed output at `index` is not the permutation of its input.
+    #[error("output {index} is not the poseidon2 permutation of its input")]
+    WrongOutput { index: usize },
+
+    /// The FRI parameters are below `FriParams::MIN_SECURITY_BITS`, so the `p3agg` program would
+    /// reject the proof.
+    #[error("fri parameters give {bits} bits of security, below the minimum of {min}")]
+    InsecureFri { bits: usize, min: usize },
+}
+
+pub type P3Result<T> = Result<T, P3ProveError>;
@@ -1 +1,3 @@
+pub mod error;
+pub mod p3_air;
 pub mod sp1_p3_poseidon2;
\ No newline at end of file
@@ -0,0 +1,203 @@
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use p3_air::Air;
+use p3_baby_bear::BabyBear;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_uni_stark::{ProverConstraintFolder, SymbolicAirBuilder, VerifierConstraintFolder};
+use serde::{Deserialize, Serialize};
+use sp1_core::air::machine::MachineAir;
+use sp1_p3_type::types::{chip_id::P3ChipId, fri_config::{FriParams, P3Proof, P3StarkConfig}};
+use sp1_recursion_core::{poseidon2::{Poseidon2Chip, Poseidon2Event}, poseidon2_wide::Poseidon2WideChip, runtime::ExecutionRecord};
+
+type SC = P3StarkConfig;
+
+/// What proving a trace took, for callers and benchmarks to record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct P3ProveMetrics {
+    pub trace_width: usize,
+    pub trace_height: usize,
+    pub prove_duration: Duration,
+    /// `None` unless the proof was verified after proving.
+    pub verify_duration: Option<Duration>,
+    /// The bincode size of the `P3Proof`, as written to the `p3agg` program.
+    pub proof_size: u64,
+}
+
+/// A p3 proof with its public values and the metrics of proving it.
+pub struct P3ProveResult {
+    pub proof: P3Proof,
+    pub public_values: Vec<BabyBear>,
+    pub metrics: P3ProveMetrics,
+}
+
+/// Proves `trace` against any `air` with the FRI parameters `fri`, then verifies the proof if
+/// `verify` is set, panicking if it is invalid.
+pub fn prove_air<A>(
+    air: &A,
+    trace: RowMajorMatrix<BabyBear>,
+    public_values: &Vec<BabyBear>,
+    fri: FriParams,
+    verify: bool,
+) -> P3ProveResult
+where
+    A: Air<SymbolicAirBuilder<BabyBear>>
+        + for<'a> Air<ProverConstraintFolder<'a, SC>>
+        + for<'a> Air<VerifierConstraintFolder<'a, SC>>,
+{
+    let (trace_width, trace_height) = (trace.width(), trace.height());
+
+    let start = Instant::now();
+    let config = fri.stark_config();
+    let mut challenger = fri.challenger();
+    let proof = p3_uni_stark::prove(&config, air, &mut challenger, trace, public_values);
+    let prove_duration = start.elapsed();
+
+    let proof = P3Proof { fri, proof };
+    let verify_duration = verify.then(|| {
+        let start = Instant::now();
+        verify_air(air, &proof, public_values).expect("expected proof to be valid");
+        start.elapsed()
+    });
+    let proof_size = bincode::serialized_size(&proof).expect("serialize p3 proof error");
+
+    P3ProveResult {
+        proof,
+        public_values: public_values.clone(),
+        metrics: P3ProveMetrics {
+            trace_width,
+            trace_height,
+            prove_duration,
+            verify_duration,
+            proof_size,
+        },
+    }
+}
+
+/// Verifies `proof` of any `air` against its `public_values`, with a fresh challenger and the FRI
+/// parameters carried by the proof.
+pub fn verify_air<A>(air: &A, proof: &P3Proof, public_values: &Vec<BabyBear>) -> Result<(), p3_uni_stark::VerificationError>
+where
+    A: Air<SymbolicAirBuilder<BabyBear>> + for<'a> Air<VerifierConstraintFolder<'a, SC>>,
+{
+    let config = proof.fri.stark_config();
+    let mut challenger = proof.fri.challenger();
+    p3_uni_stark::verify(&config, air, &mut challenger, &proof.proof, public_values)
+}
+
+/// Verifies `proof` of the chip named by `id` against its `public_values`.
+pub fn verify_chip(id: P3ChipId, proof: &P3Proof, public_values: &Vec<BabyBear>) -> Result<(), p3_uni_stark::VerificationError> {
+    match id {
+        P3ChipId::Poseidon2 => verify_air(
+            &Poseidon2Chip {
+                fixed_log2_rows: None,
+                pad: true,
+            },
+            proof,
+            public_values,
+        ),
+        P3ChipId::Poseidon2Wide3 => verify_air(
+            &Poseidon2WideChip::<3> {
+                fixed_log2_rows: None,
+                pad: true,
+            },
+            proof,
+            public_values,
+        ),
+        P3ChipId::Poseidon2Wide7 => verify_air(
+            &Poseidon2WideChip::<7> {
+                fixed_log2_rows: None,
+                pad: true,
+            },
+            proof,
+            public_values,
+        ),
+    }
+}
+
+/// The trace of `chip` over the Poseidon2 permutations of `inputs` into `outputs`, which panics if
+/// their lengths differ; `sp1_p3_poseidon2::check_permutations` rejects such batches first.
+pub fn poseidon2_trace<A: MachineAir<BabyBear, Record = ExecutionRecord<BabyBear>>>(
+    chip: &A,
+    inputs: Vec<[BabyBear; 16]>,
+    outputs: Vec<[BabyBear; 16]>,
+) -> RowMajorMatrix<BabyBear> {
+    let mut input_exec = ExecutionRecord::<BabyBear>::default();
+    for (input, output) in inputs.into_iter().zip_eq(outputs) {
+        input_exec
+            .poseidon2_events
+            .push(Poseidon2Event::dummy_from_input(input, output));
+    }
+
+    chip.generate_trace(&input_exec, &mut ExecutionRecord::<BabyBear>::default())
+}
+
+/// Proves the Poseidon2 permutations of `inputs` into `outputs` with the chip named by `id`,
+/// verifying the proof too if `verify` is set.
+pub fn prove_chip(
+    id: P3ChipId,
+    fri: FriParams,
+    inputs: Vec<[BabyBear; 16]>,
+    outputs: Vec<[BabyBear; 16]>,
+    public_values: &Vec<BabyBear>,
+    verify: bool,
+) -> P3ProveResult {
+    match id {
+        P3ChipId::Poseidon2 => {
+            let chip = Poseidon2Chip {
+                fixed_log2_rows: None,
+                pad: true,
+            };
+            prove_air(&chip, poseidon2_trace(&chip, inputs, outputs), public_values, fri, verify)
+        }
+        P3ChipId::Poseidon2Wide3 => {
+            let chip = Poseidon2WideChip::<3> {
+                fixed_log2_rows: None,
+                pad: true,
+            };
+            prove_air(&chip, poseidon2_trace(&chip, inputs, outputs), public_values, fri, verify)
+        }
+        P3ChipId::Poseidon2Wide7 => {
+            let chip = Poseidon2WideChip::<7> {
+                fixed_log2_rows: None,
+                pad: true,
+            };
+            prove_air(&chip, poseidon2_trace(&chip, inputs, outputs), public_values, fri, verify)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::sp1_p3_poseidon2::random_permutations;
+
+    #[test]
+    fn prove_wide_chips() {
+        let (test_inputs, expected_outputs) = random_permutations(16);
+
+        for id in [P3ChipId::Poseidon2Wide3, P3ChipId::Poseidon2Wide7] {
+            let result = prove_chip(id, FriParams::COMPRESSED, test_inputs.clone(), expected_outputs.clone(), &vec![], false);
+            assert!(result.metrics.verify_duration.is_none());
+            assert!(verify_chip(id, &result.proof, &vec![]).is_ok());
+        }
+    }
+
+    #[test]
+    fn fri_params_must_match() {
+        let (test_inputs, expected_outputs) = random_permutations(16);
+
+        let custom = FriParams {
+            log_blowup: 2,
+            num_queries: 50,
+            proof_of_work_bits: 16,
+        };
+        let mut proof = prove_chip(P3ChipId::Poseidon2, custom, test_inputs, expected_outputs, &vec![], true).proof;
+        assert!(verify_chip(P3ChipId::Poseidon2, &proof, &vec![]).is_ok());
+
+        proof.fri = FriParams::DEFAULT;
+        assert!(verify_chip(P3ChipId::Poseidon2, &proof, &vec![]).is_err());
+    }
+}
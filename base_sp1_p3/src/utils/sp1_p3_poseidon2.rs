@@ -1,110 +1,126 @@
-use std::time::Instant;
-use itertools::Itertools;
-use p3_matrix::Matrix;
-
 use p3_baby_bear::BabyBear;
-use p3_baby_bear::DiffusionMatrixBabyBear;
-use p3_matrix::dense::RowMajorMatrix;
-use p3_poseidon2::Poseidon2;
-use p3_poseidon2::Poseidon2ExternalMatrixGeneral;
-
-use p3_uni_stark::Proof;
-use sp1_core::stark::UniConfig;
-use sp1_core::utils::uni_stark_verify;
-use sp1_recursion_core::{poseidon2::{Poseidon2Chip, Poseidon2Event}, runtime::ExecutionRecord};
-
-use sp1_core::{air::machine::MachineAir, stark::StarkGenericConfig, utils::BabyBearPoseidon2};
-
-pub fn prove_babybear(inputs: Vec<[BabyBear; 16]>, outputs: Vec<[BabyBear; 16]>) -> Proof<UniConfig<BabyBearPoseidon2>> {
-    let mut input_exec = ExecutionRecord::<BabyBear>::default();
-    for (input, output) in inputs.into_iter().zip_eq(outputs) {
-        input_exec
-            .poseidon2_events
-            .push(Poseidon2Event::dummy_from_input(input, output));
+use p3_symmetric::Permutation;
+use sp1_core::utils::inner_perm;
+use sp1_p3_type::types::{chip_id::P3ChipId, fri_config::FriParams};
+use zkhash::ark_ff::UniformRand;
+
+use super::error::{P3ProveError, P3Result};
+use super::p3_air::{prove_chip, P3ProveResult};
+
+/// `n` random inputs and their Poseidon2 permutations, a batch that `check_permutations` accepts.
+pub fn random_permutations(n: usize) -> (Vec<[BabyBear; 16]>, Vec<[BabyBear; 16]>) {
+    let rng = &mut rand::thread_rng();
+    let inputs: Vec<[BabyBear; 16]> = (0..n)
+        .map(|_| core::array::from_fn(|_| BabyBear::rand(rng)))
+        .collect();
+
+    let perm = inner_perm();
+    let outputs = inputs.iter().map(|input| perm.permute(*input)).collect();
+    (inputs, outputs)
+}
+
+/// Checks that there is an output for every input, and that it is the Poseidon2 permutation of
+/// that input, so a bad batch is rejected before proving it.
+pub fn check_permutations(inputs: &[[BabyBear; 16]], outputs: &[[BabyBear; 16]]) -> P3Result<()> {
+    if inputs.len() != outputs.len() {
+        return Err(P3ProveError::LengthMismatch {
+            inputs: inputs.len(),
+            outputs: outputs.len(),
+        });
+    }
+
+    let perm = inner_perm();
+    match inputs
+        .iter()
+        .zip(outputs)
+        .position(|(input, output)| perm.permute(*input) != *output)
+    {
+        Some(index) => Err(P3ProveError::WrongOutput { index }),
+        None => Ok(()),
+    }
+}
+
+/// Proves the Poseidon2 permutations of `inputs` into `outputs` with the `Poseidon2Chip` and the
+/// compressed FRI parameters, then verifies the proof. The result holds the proof and the
+/// metrics of proving it.
+///
+/// Fails without proving if `check_permutations` rejects the batch.
+///
+/// The Poseidon2 chips constrain no public values, so the proof has none: it shows that some
+/// trace of permutations is valid, not which outputs it holds.
+pub fn prove_babybear(inputs: Vec<[BabyBear; 16]>, outputs: Vec<[BabyBear; 16]>) -> P3Result<P3ProveResult> {
+    prove_babybear_with(P3ChipId::Poseidon2, FriParams::COMPRESSED, inputs, outputs, true)
+}
+
+/// `prove_babybear` with any of the Poseidon2 chips and FRI parameters, only verifying the proof
+/// if `verify` is set. Also fails without proving if `fri` is below
+/// `FriParams::MIN_SECURITY_BITS`.
+pub fn prove_babybear_with(
+    id: P3ChipId,
+    fri: FriParams,
+    inputs: Vec<[BabyBear; 16]>,
+    outputs: Vec<[BabyBear; 16]>,
+    verify: bool,
+) -> P3Result<P3ProveResult> {
+    if !fri.is_secure() {
+        return Err(P3ProveError::InsecureFri {
+            bits: fri.security_bits(),
+            min: FriParams::MIN_SECURITY_BITS,
+        });
     }
+    check_permutations(&inputs, &outputs)?;
 
-    let chip = Poseidon2Chip {
-        fixed_log2_rows: None,
-        pad: true,
-    };
-    let trace: RowMajorMatrix<BabyBear> =
-        chip.generate_trace(&input_exec, &mut ExecutionRecord::<BabyBear>::default());
-    println!(
-        "trace dims is width: {:?}, height: {:?}",
-        trace.width(),
-        trace.height()
-    );
-
-    let start = Instant::now();
-    let config = BabyBearPoseidon2::compressed();
-    let mut challenger = config.challenger();
-    // let proof: Proof<UniConfig<BabyBearPoseidon2>> = uni_stark_prove(&config, &chip, &mut challenger, trace);
-    let proof : Proof<UniConfig<BabyBearPoseidon2>> = p3_uni_stark::prove(&UniConfig(config.clone()), &chip, &mut challenger, trace, &vec![]);
-    let duration = start.elapsed().as_secs_f64();
-    println!("proof duration = {:?}", duration);
-
-    let mut challenger: p3_challenger::DuplexChallenger<
-        BabyBear,
-        Poseidon2<BabyBear, Poseidon2ExternalMatrixGeneral, DiffusionMatrixBabyBear, 16, 7>,
-        16,
-        8,
-    > = config.challenger();
-    let start = Instant::now();
-    uni_stark_verify(&config, &chip, &mut challenger, &proof)
-        .expect("expected proof to be valid");
-
-    let duration = start.elapsed().as_secs_f64();
-    println!("verify duration = {:?}", duration);
-
-    proof
+    Ok(prove_chip(id, fri, inputs, outputs, &Vec::new(), verify))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use itertools::Itertools;
-    use sp1_core::utils::inner_perm;
-    use zkhash::ark_ff::UniformRand;
-
-    use p3_symmetric::Permutation;
-
     #[test]
     fn prove_babybear_success() {
-        let rng = &mut rand::thread_rng();
-
-        let test_inputs: Vec<[BabyBear; 16]> = (0..256)
-            .map(|_| core::array::from_fn(|_| BabyBear::rand(rng)))
-            .collect_vec();
-
-        let gt: Poseidon2<
-            BabyBear,
-            Poseidon2ExternalMatrixGeneral,
-            DiffusionMatrixBabyBear,
-            16,
-            7,
-        > = inner_perm();
-
-        let expected_outputs = test_inputs
-            .iter()
-            .map(|input| gt.permute(*input))
-            .collect::<Vec<_>>();
-
-        let _ = prove_babybear(test_inputs, expected_outputs);
+        let (test_inputs, expected_outputs) = random_permutations(256);
+
+        let result = prove_babybear(test_inputs, expected_outputs).unwrap();
+        assert!(result.public_values.is_empty());
+        assert!(result.metrics.trace_height.is_power_of_two());
+        assert!(result.metrics.verify_duration.is_some());
+        assert!(result.metrics.proof_size > 0);
     }
 
     #[test]
-    #[should_panic]
     fn prove_babybear_failure() {
-        let rng = &mut rand::thread_rng();
-        let test_inputs: Vec<[BabyBear; 16]> = (0..16)
-            .map(|_| core::array::from_fn(|_| BabyBear::rand(rng)))
-            .collect_vec();
+        let (test_inputs, _) = random_permutations(16);
+        let (_, bad_outputs) = random_permutations(16);
+
+        assert_eq!(
+            prove_babybear(test_inputs, bad_outputs).err(),
+            Some(P3ProveError::WrongOutput { index: 0 })
+        );
+    }
 
-        let bad_outputs: Vec<[BabyBear; 16]> = (0..16)
-            .map(|_| core::array::from_fn(|_| BabyBear::rand(rng)))
-            .collect_vec();
+    #[test]
+    fn prove_babybear_insecure_fri() {
+        let (test_inputs, expected_outputs) = random_permutations(16);
+
+        let weak = FriParams {
+            log_blowup: 1,
+            num_queries: 50,
+            proof_of_work_bits: 16,
+        };
+        assert_eq!(
+            prove_babybear_with(P3ChipId::Poseidon2, weak, test_inputs, expected_outputs, false).err(),
+            Some(P3ProveError::InsecureFri { bits: 66, min: 100 })
+        );
+    }
 
-        let _ = prove_babybear(test_inputs, bad_outputs);
+    #[test]
+    fn prove_babybear_length_mismatch() {
+        let (test_inputs, expected_outputs) = random_permutations(16);
+
+        assert_eq!(
+            prove_babybear(test_inputs, expected_outputs[..15].to_vec()).err(),
+            Some(P3ProveError::LengthMismatch { inputs: 16, outputs: 15 })
+        );
     }
-}
\ No newline at end of file
+}
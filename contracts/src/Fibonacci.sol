@@ -15,16 +15,20 @@ contract Fibonacci is SP1Verifier {
         fibonacciProgramVkey = _fibonacciProgramVkey;
     }
 
+    /// @notice The cook selector tag committed by the program for the fibonacci cook.
+    uint8 public constant FIBONACCI_COOK = 0;
+
     /// @notice The entrypoint for verifying the proof of a fibonacci number.
     /// @param proof The encoded proof.
-    /// @param publicValues The encoded public values.
+    /// @param publicValues The encoded public values, prefixed by the cook selector tag.
     function verifyFibonacciProof(
-        bytes memory proof,
-        bytes memory publicValues
+        bytes calldata proof,
+        bytes calldata publicValues
     ) public view returns (uint32, uint32, uint32) {
         this.verifyProof(fibonacciProgramVkey, publicValues, proof);
+        require(uint8(publicValues[0]) == FIBONACCI_COOK, "not a fibonacci proof");
         (uint32 n, uint32 a, uint32 b) = abi.decode(
-            publicValues,
+            publicValues[1:],
             (uint32, uint32, uint32)
         );
         return (n, a, b);
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.13;
+
+/// @title TxidMerkle.
+/// @notice Verifies that a txid is in a batch proved by the `sp1eip712` program with `--txid-root`,
+///         which commits only the keccak256 merkle root of the batch txids and their count.
+/// @dev The tree is built over the txids zero-padded to the next power of two, and every inner
+///      node is `keccak256(left || right)`. The proofs are saved by `sp1-cook eip712 --txid-root`.
+library TxidMerkle {
+    /// @notice The number of siblings in the proof of a txid in a batch of `count` txids.
+    function depth(uint256 count) internal pure returns (uint256 levels) {
+        while ((uint256(1) << levels) < count) {
+            levels++;
+        }
+    }
+
+    /// @notice Whether `txid` is the `index`-th txid of the batch committed as `root` and `count`.
+    /// @param proof The siblings of the path from the txid up to the root.
+    function verify(bytes32 root, uint256 count, uint256 index, bytes32 txid, bytes32[] memory proof)
+        internal
+        pure
+        returns (bool)
+    {
+        if (index >= count || proof.length != depth(count)) {
+            return false;
+        }
+
+        bytes32 node = txid;
+        for (uint256 i = 0; i < proof.length; i++) {
+            if (index & 1 == 0) {
+                node = keccak256(abi.encodePacked(node, proof[i]));
+            } else {
+                node = keccak256(abi.encodePacked(proof[i], node));
+            }
+            index >>= 1;
+        }
+
+        return node == root;
+    }
+}
@@ -12,7 +12,7 @@ struct SP1ProofFixtureJson {
     uint32 n;
     bytes proof;
     bytes publicValues;
-    bytes32 vkey;
+    bytes32 vkey_hash;
 }
 
 contract FibonacciTest is Test {
@@ -22,15 +22,21 @@ contract FibonacciTest is Test {
 
     function loadFixture() public view returns (SP1ProofFixtureJson memory) {
         string memory root = vm.projectRoot();
-        string memory path = string.concat(root, "/src/fixtures/fixture.json");
+        string memory path = string.concat(root, "/src/fixtures/fibonacci-fixture.json");
         string memory json = vm.readFile(path);
-        bytes memory jsonBytes = json.parseRaw(".");
-        return abi.decode(jsonBytes, (SP1ProofFixtureJson));
+        return SP1ProofFixtureJson({
+            a: uint32(json.readUint(".decoded.a")),
+            b: uint32(json.readUint(".decoded.b")),
+            n: uint32(json.readUint(".decoded.n")),
+            proof: json.readBytes(".proof"),
+            publicValues: json.readBytes(".public_values"),
+            vkey_hash: json.readBytes32(".vkey_hash")
+        });
     }
 
     function setUp() public {
         SP1ProofFixtureJson memory fixture = loadFixture();
-        fibonacci = new Fibonacci(fixture.vkey);
+        fibonacci = new Fibonacci(fixture.vkey_hash);
     }
 
     function test_ValidFibonacciProof() public view {
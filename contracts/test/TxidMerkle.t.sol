@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.25;
+
+import {Test} from "forge-std/Test.sol";
+import {stdJson} from "forge-std/StdJson.sol";
+import {TxidMerkle} from "../src/TxidMerkle.sol";
+
+contract TxidMerkleTest is Test {
+    // The root of the txids 0x0101.., 0x0202.. and 0x0303.., as committed by `sp1eip712`.
+    bytes32 constant ROOT = 0xb3eab8646d706247bc8e454e8850ea39af6dbe9bdc9c7751778da0e95d177c34;
+    uint256 constant COUNT = 3;
+
+    function txid(uint8 i) internal pure returns (bytes32) {
+        return bytes32(uint256(i) * 0x0101010101010101010101010101010101010101010101010101010101010101);
+    }
+
+    function proofOfLast() internal pure returns (bytes32[] memory proof) {
+        proof = new bytes32[](2);
+        // The zero leaf padding the batch to four txids.
+        proof[0] = bytes32(0);
+        proof[1] = 0x346d8c96a2454213fcc0daff3c96ad0398148181b9fa6488f7ae2c0af5b20aa0;
+    }
+
+    function test_VerifyTxid() public pure {
+        assertTrue(TxidMerkle.verify(ROOT, COUNT, 2, txid(3), proofOfLast()));
+    }
+
+    function test_VerifyFirstTxid() public pure {
+        bytes32[] memory proof = new bytes32[](2);
+        proof[0] = txid(2);
+        proof[1] = keccak256(abi.encodePacked(txid(3), bytes32(0)));
+        assertTrue(TxidMerkle.verify(ROOT, COUNT, 0, txid(1), proof));
+    }
+
+    function test_RejectWrongTxid() public pure {
+        assertFalse(TxidMerkle.verify(ROOT, COUNT, 2, txid(2), proofOfLast()));
+    }
+
+    function test_RejectPaddingLeaf() public pure {
+        // The padding leaf is in the tree, but beyond the committed count.
+        bytes32[] memory proof = new bytes32[](2);
+        proof[0] = txid(3);
+        proof[1] = 0x346d8c96a2454213fcc0daff3c96ad0398148181b9fa6488f7ae2c0af5b20aa0;
+        assertFalse(TxidMerkle.verify(ROOT, COUNT, 3, bytes32(0), proof));
+    }
+
+    function test_RejectWrongProofLength() public pure {
+        bytes32[] memory proof = new bytes32[](3);
+        proof[1] = 0x346d8c96a2454213fcc0daff3c96ad0398148181b9fa6488f7ae2c0af5b20aa0;
+        assertFalse(TxidMerkle.verify(ROOT, COUNT, 2, txid(3), proof));
+    }
+}
+
+/// Checks the proofs saved by `sp1-cook eip712 --txid-root` against the root they were saved with.
+contract TxidProofsTest is Test {
+    using stdJson for string;
+
+    function loadProofs() public view returns (string memory) {
+        string memory root = vm.projectRoot();
+        string memory path = string.concat(root, "/src/fixtures/eip712-txid-proofs.json");
+        return vm.readFile(path);
+    }
+
+    function proofAt(string memory json, uint256 i)
+        internal
+        pure
+        returns (uint256 index, bytes32 txid, bytes32[] memory siblings)
+    {
+        string memory key = string.concat(".proofs[", vm.toString(i), "]");
+        index = json.readUint(string.concat(key, ".index"));
+        txid = json.readBytes32(string.concat(key, ".leaf"));
+        siblings = json.readBytes32Array(string.concat(key, ".siblings"));
+    }
+
+    function test_VerifySavedTxidProofs() public view {
+        string memory json = loadProofs();
+        bytes32 root = json.readBytes32(".root");
+        uint256 count = json.readUint(".count");
+        assertGt(count, 0);
+
+        for (uint256 i = 0; i < count; i++) {
+            (uint256 index, bytes32 txid, bytes32[] memory siblings) = proofAt(json, i);
+            assertEq(index, i);
+            assertTrue(TxidMerkle.verify(root, count, index, txid, siblings));
+        }
+    }
+
+    function test_RejectTamperedSavedTxid() public view {
+        string memory json = loadProofs();
+        bytes32 root = json.readBytes32(".root");
+        uint256 count = json.readUint(".count");
+
+        (uint256 index, bytes32 txid, bytes32[] memory siblings) = proofAt(json, 0);
+        assertFalse(TxidMerkle.verify(root, count, index, txid ^ bytes32(uint256(1)), siblings));
+    }
+}
@@ -19,10 +19,13 @@ contract EcRecoverTest is Test {
 
     function loadFixture() public view returns (EcRecoverProofFixtureJson memory) {
         string memory root = vm.projectRoot();
-        string memory path = string.concat(root, "/src/fixtures/ecr-fixture.json");
+        string memory path = string.concat(root, "/src/fixtures/ecrecover-fixture.json");
         string memory json = vm.readFile(path);
-        bytes memory jsonBytes = json.parseRaw(".");
-        return abi.decode(jsonBytes, (EcRecoverProofFixtureJson));
+        return EcRecoverProofFixtureJson({
+            proof: json.readBytes(".proof"),
+            publicValues: json.readBytes(".public_values"),
+            vkey_hash: json.readBytes32(".vkey_hash")
+        });
     }
 
     function setUp() public {
@@ -6,6 +6,13 @@ import {stdJson} from "forge-std/StdJson.sol";
 import {AnyVerifier} from "../src/anyverifier.sol";
 import {SP1Verifier} from "@sp1-contracts/SP1Verifier.sol";
 
+struct TxRecord {
+    bytes32 txid;
+    bytes32 digest;
+    address signer;
+    uint8 verdict;
+}
+
 struct AnyProofFixtureJson {
     bytes proof;
     bytes publicValues;
@@ -17,12 +24,18 @@ contract Eip712Test is Test {
 
     AnyVerifier public any_verifier;
 
+    bytes32 constant DOMAIN_TYPEHASH =
+        keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)");
+
     function loadFixture() public view returns (AnyProofFixtureJson memory) {
         string memory root = vm.projectRoot();
         string memory path = string.concat(root, "/src/fixtures/eip712-fixture.json");
         string memory json = vm.readFile(path);
-        bytes memory jsonBytes = json.parseRaw(".");
-        return abi.decode(jsonBytes, (AnyProofFixtureJson));
+        return AnyProofFixtureJson({
+            proof: json.readBytes(".proof"),
+            publicValues: json.readBytes(".public_values"),
+            vkey_hash: json.readBytes32(".vkey_hash")
+        });
     }
 
     function setUp() public {
@@ -37,6 +50,43 @@ contract Eip712Test is Test {
         );
     }
 
+    function test_CommittedDomainSeparator() public view {
+        AnyProofFixtureJson memory fixture = loadFixture();
+
+        // The default domain of `sp1-cook eip712`.
+        bytes32 domainSeparator = keccak256(
+            abi.encode(
+                DOMAIN_TYPEHASH, keccak256(bytes("Omniverse Transaction")), keccak256(bytes("1")), uint256(1), address(0)
+            )
+        );
+
+        bytes memory publicValues = fixture.publicValues;
+        bytes32 committed;
+        assembly {
+            committed := mload(add(publicValues, 32))
+        }
+        assertEq(committed, domainSeparator);
+    }
+
+    function test_DecodeTxRecords() public view {
+        AnyProofFixtureJson memory fixture = loadFixture();
+
+        (, bool withVerdicts, bytes32 txidRoot, uint256 count, TxRecord[] memory records) =
+            abi.decode(fixture.publicValues, (bytes32, bool, bytes32, uint256, TxRecord[]));
+        assertGt(count, 0);
+        // With `--txid-root` only the root of the txids is committed, see `TxidMerkleTest`.
+        if (txidRoot != bytes32(0)) {
+            assertEq(records.length, 0);
+            return;
+        }
+        assertEq(records.length, count);
+        for (uint256 i = 0; i < records.length; i++) {
+            if (!withVerdicts || records[i].verdict == 0) {
+                assertTrue(records[i].signer != address(0));
+            }
+        }
+    }
+
     function testFail_InvalidEcRecoverProof() public view {
         AnyProofFixtureJson memory fixture = loadFixture();
 
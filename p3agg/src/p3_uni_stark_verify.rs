@@ -1,28 +1,113 @@
-use p3_baby_bear::{BabyBear, DiffusionMatrixBabyBear};
-use p3_uni_stark::Proof;
-use p3_poseidon2::Poseidon2;
-use p3_poseidon2::Poseidon2ExternalMatrixGeneral;
-
-use sp1_core::{stark::{StarkGenericConfig, UniConfig}, utils::BabyBearPoseidon2};
-use sp1_recursion_core::poseidon2::Poseidon2Chip;
-
-pub fn verify_babybear(proof: Proof<UniConfig<BabyBearPoseidon2>>){
-    let config = BabyBearPoseidon2::compressed();
-    
-    // todo: read public values. The values are `baby bear`s
-
-    let mut challenger: p3_challenger::DuplexChallenger<
-        BabyBear,
-        Poseidon2<BabyBear, Poseidon2ExternalMatrixGeneral, DiffusionMatrixBabyBear, 16, 7>,
-        16,
-        8,
-    > = config.challenger();
-
-    let chip = Poseidon2Chip {
-        fixed_log2_rows: None,
-        pad: true,
-    };
-
-    p3_uni_stark::verify(&UniConfig(config.clone()), &chip, &mut challenger, &proof, &vec![])
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolType};
+use p3_air::Air;
+use p3_baby_bear::BabyBear;
+use p3_uni_stark::{SymbolicAirBuilder, VerifierConstraintFolder};
+
+use sp1_p3_type::types::{chip_id::P3ChipId, fri_config::{FriParams, P3Proof, P3StarkConfig}};
+use sp1_recursion_core::{poseidon2::Poseidon2Chip, poseidon2_wide::Poseidon2WideChip};
+use tiny_keccak::{Hasher, Keccak};
+
+type SC = P3StarkConfig;
+
+/// The chip id, the FRI parameters and the keccak256 of the bincode uni-STARK proof of one p3
+/// proof, whose keccak256 is the digest of the proof,
+/// `keccak256(abi.encode(chip, logBlowup, numQueries, powBits, proofHash))` in Solidity.
+pub type ProofValuesTuple = sol! {
+    tuple(uint8, uint32, uint32, uint32, bytes32)
+};
+
+/// The chip ids and digests of every verified p3 proof, in order, so Solidity can
+/// `abi.decode(publicValues, (uint8[], bytes32[]))`.
+pub type PublicValuesTuple = sol! {
+    tuple(uint8[], bytes32[])
+};
+
+/// Verifies `proof` of any `air` against its BabyBear `public_values`, with the FRI parameters
+/// carried by the proof, rejecting parameters below `FriParams::MIN_SECURITY_BITS`.
+pub fn verify_air<A>(air: &A, proof: &P3Proof, public_values: &Vec<BabyBear>)
+where
+    A: Air<SymbolicAirBuilder<BabyBear>> + for<'a> Air<VerifierConstraintFolder<'a, SC>>,
+{
+    assert!(
+        proof.fri.is_secure(),
+        "p3 proof fri parameters give {} bits of security, below {}",
+        proof.fri.security_bits(),
+        FriParams::MIN_SECURITY_BITS
+    );
+
+    let config = proof.fri.stark_config();
+    let mut challenger = proof.fri.challenger();
+
+    p3_uni_stark::verify(&config, air, &mut challenger, &proof.proof, public_values)
         .expect("expected proof to be valid");
 }
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(bytes);
+    let mut digest = [0u8; 32];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+/// Verifies `proof` of the chip named by `tag` with a fresh challenger, returning the digest of
+/// the chip, the FRI parameters and the proof.
+///
+/// The Poseidon2 chips constrain no public values, so the proof is verified against none, and the
+/// permutation outputs cannot be committed until an AIR exposes them.
+pub fn verify_babybear(tag: u8, proof: P3Proof) -> B256 {
+    let public_values = Vec::new();
+    match P3ChipId::from_tag(tag).expect("unknown p3 chip") {
+        P3ChipId::Poseidon2 => verify_air(
+            &Poseidon2Chip {
+                fixed_log2_rows: None,
+                pad: true,
+            },
+            &proof,
+            &public_values,
+        ),
+        P3ChipId::Poseidon2Wide3 => verify_air(
+            &Poseidon2WideChip::<3> {
+                fixed_log2_rows: None,
+                pad: true,
+            },
+            &proof,
+            &public_values,
+        ),
+        P3ChipId::Poseidon2Wide7 => verify_air(
+            &Poseidon2WideChip::<7> {
+                fixed_log2_rows: None,
+                pad: true,
+            },
+            &proof,
+            &public_values,
+        ),
+    }
+
+    let proof_hash = keccak256(&bincode::serialize(&proof.proof).expect("serialize p3 proof error"));
+    let fri = proof.fri;
+    B256::from(keccak256(&ProofValuesTuple::abi_encode_params(&(
+        tag,
+        fri.log_blowup as u32,
+        fri.num_queries as u32,
+        fri.proof_of_work_bits as u32,
+        B256::from(proof_hash),
+    ))))
+}
+
+/// Reads a count of p3 proofs, then the chip tag and proof (with its FRI parameters) of each,
+/// verifies them all, and commits their chip ids and digests as a `PublicValuesTuple`.
+pub fn verify_batch() {
+    let num_proofs = sp1_zkvm::io::read::<usize>();
+
+    let (tags, digests): (Vec<u8>, Vec<B256>) = (0..num_proofs)
+        .map(|_| {
+            let tag = sp1_zkvm::io::read::<u8>();
+            let proof = sp1_zkvm::io::read::<P3Proof>();
+            (tag, verify_babybear(tag, proof))
+        })
+        .unzip();
+
+    sp1_zkvm::io::commit_slice(&PublicValuesTuple::abi_encode_params(&(tags, digests)));
+}
@@ -20,5 +20,9 @@ pub fn ecdsa_recover() {
         let recovred_pk = secp256k1::ecrecover(&signature_vu8, &msg_digest).expect("recover public key error");
 
         assert_eq!(pk_slice, recovred_pk, "Invalid signature");
+
+        // commit to every recovered signer: `msg_digest || pk`
+        sp1_zkvm::io::commit_slice(&msg_digest);
+        sp1_zkvm::io::commit_slice(&recovred_pk);
     }
 }
\ No newline at end of file
@@ -3,4 +3,37 @@ pub mod keccak256;
 pub mod poseidon_hash;
 pub mod verify_ecdsa;
 pub mod ecdsa_recover;
-pub mod verify_p2_proof;
\ No newline at end of file
+pub mod verify_p2_proof;
+
+/// The tag read from `sp1_zkvm::io` before any cook input, selecting which cook the guest runs.
+///
+/// The tag is also committed as the first byte of the public values, so keep the host-side
+/// `fibonacci_script::utils::cook_selector::CookSelector` in sync with this one.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookSelector {
+    Fibonacci = 0,
+    Keccak256 = 1,
+    Poseidon = 2,
+    VerifyEcdsa = 3,
+    EcdsaRecover = 4,
+    VerifyPlonky2Proof = 5,
+}
+
+impl CookSelector {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(CookSelector::Fibonacci),
+            1 => Some(CookSelector::Keccak256),
+            2 => Some(CookSelector::Poseidon),
+            3 => Some(CookSelector::VerifyEcdsa),
+            4 => Some(CookSelector::EcdsaRecover),
+            5 => Some(CookSelector::VerifyPlonky2Proof),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        *self as u8
+    }
+}
@@ -20,6 +20,10 @@ pub fn verify_ecdsa() {
     // `verify_signature` relates to `sign_prehash`
     assert!(verify_signature(&pk_slice, &msg_digest, &signature, None), "Invalid signature");
 
+    // commit to what was verified: `msg_digest || pk`
+    sp1_zkvm::io::commit_slice(&msg_digest);
+    sp1_zkvm::io::commit_slice(&pk_slice);
+
     // `verify` is related to `sign`
     // let public_key = PublicKey::from_sec1_bytes(&pk_slice);
     // let public_key = public_key.unwrap();
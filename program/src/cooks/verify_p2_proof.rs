@@ -1,24 +1,105 @@
-use plonky2::{plonk::{circuit_data::{CommonCircuitData, VerifierCircuitData, VerifierOnlyCircuitData}, config::{GenericConfig, PoseidonGoldilocksConfig}, proof::ProofWithPublicInputs}, util::serialization::DefaultGateSerializer};
+use plonky2::{hash::{hash_types::RichField, poseidon::PoseidonHash}, plonk::{circuit_data::{CommonCircuitData, VerifierCircuitData, VerifierOnlyCircuitData}, config::{GenericConfig, GenericHashOut, Hasher, KeccakGoldilocksConfig, PoseidonGoldilocksConfig}, proof::ProofWithPublicInputs}, util::serialization::DefaultGateSerializer};
+use plonky2_field::goldilocks_field::GoldilocksField;
+use tiny_keccak::{Hasher as _, Keccak};
 
+const D: usize = 2;
 
+/// The tag read before the plonky2 proofs, selecting the config they are verified with.
+///
+/// Keep the host-side `fibonacci_script::utils::p2_proof::P2Config` in sync with this one.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum P2Config {
+    PoseidonGoldilocks = 0,
+    KeccakGoldilocks = 1,
+}
+
+impl P2Config {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(P2Config::PoseidonGoldilocks),
+            1 => Some(P2Config::KeccakGoldilocks),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a `P2Config` tag, a count of plonky2 proofs and a count of circuits, then for every
+/// circuit its digest, verifier only data, common data bytes, and a count and list of its proofs,
+/// each after its index in the inputs.
+///
+/// The common data of a circuit is decoded once for all of its proofs of the group. It is read as
+/// the full gate serialized bytes of plonky2, no compact encoding of it is supported. Commits the
+/// tag and `(circuit digest, constants sigmas cap hash, common data hash, public inputs hash)` of
+/// every proof, in the order of the inputs, so the verified circuit, gates included, is bound to
+/// the public values.
 pub fn verify_plonky2_proof() {
-    const D: usize = 2;
-    type C = PoseidonGoldilocksConfig;
-    type F = <C as GenericConfig<D>>::F;
+    let tag = sp1_zkvm::io::read::<u8>();
+    let digests = match P2Config::from_tag(tag).expect("unknown plonky2 config") {
+        P2Config::PoseidonGoldilocks => verify_circuits::<PoseidonGoldilocksConfig>(),
+        P2Config::KeccakGoldilocks => verify_circuits::<KeccakGoldilocksConfig>(),
+    };
+
+    sp1_zkvm::io::commit(&(tag, digests));
+}
+
+/// The canonical elements of a hash, which are 4 for both the poseidon and the 25-byte keccak hashes.
+fn hash_elements<F: RichField>(hash: impl GenericHashOut<F>) -> [u64; 4] {
+    hash.to_vec()
+        .iter()
+        .map(|e| e.to_canonical_u64())
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("unexpected hash length")
+}
 
-    let ppis = sp1_zkvm::io::read::<ProofWithPublicInputs<F, C, D>>();
-    let vod = sp1_zkvm::io::read::<VerifierOnlyCircuitData<C, D>>();
-    let ccd_bytes = sp1_zkvm::io::read_vec();
+/// `keccak256` of the gate-serialized common circuit data.
+fn common_data_hash(ccd_bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(ccd_bytes);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
 
+fn verify_circuits<C: GenericConfig<D, F = GoldilocksField>>() -> Vec<([u64; 4], [u64; 4], [u8; 32], [u64; 4])> {
+    type F = GoldilocksField;
+
+    let num_proofs = sp1_zkvm::io::read::<usize>();
+    let num_circuits = sp1_zkvm::io::read::<usize>();
     let gate_serializer = DefaultGateSerializer;
-    let common = CommonCircuitData::<F, D>::from_bytes(ccd_bytes.clone(), &gate_serializer).unwrap();
 
-    sp1_zkvm::io::commit(&ppis.public_inputs);
+    let mut digests = vec![None::<([u64; 4], [u64; 4], [u8; 32], [u64; 4])>; num_proofs];
+    for _ in 0..num_circuits {
+        let circuit_digest = sp1_zkvm::io::read::<[u64; 4]>();
+        let vod = sp1_zkvm::io::read::<VerifierOnlyCircuitData<C, D>>();
+        let ccd_bytes = sp1_zkvm::io::read_vec();
+        assert_eq!(circuit_digest, hash_elements(vod.circuit_digest), "circuit digest mismatch");
+        let constants_cap_hash = hash_elements(PoseidonHash::hash_no_pad(&vod.constants_sigmas_cap.flatten()));
+        // the circuit digest only covers the constants, the gates are in the common data
+        let common_hash = common_data_hash(&ccd_bytes);
 
-    let vd = VerifierCircuitData {
-        verifier_only: vod,
-        common
-    };
+        let common = CommonCircuitData::<F, D>::from_bytes(ccd_bytes, &gate_serializer).unwrap();
+        let vd = VerifierCircuitData {
+            verifier_only: vod,
+            common
+        };
+
+        let num_circuit_proofs = sp1_zkvm::io::read::<usize>();
+        for _ in 0..num_circuit_proofs {
+            let index = sp1_zkvm::io::read::<usize>();
+            let ppis = sp1_zkvm::io::read::<ProofWithPublicInputs<F, C, D>>();
+            let public_inputs_hash = hash_elements(ppis.get_public_inputs_hash());
+
+            vd.verify(ppis).unwrap();
+            assert!(digests[index].is_none(), "duplicate proof index");
+            digests[index] = Some((circuit_digest, constants_cap_hash, common_hash, public_inputs_hash));
+        }
+    }
 
-    vd.verify(ppis).unwrap();
-}
\ No newline at end of file
+    // back in the order of the inputs, every one of which must have been verified
+    digests
+        .into_iter()
+        .map(|digest| digest.expect("missing proof index"))
+        .collect()
+}
@@ -1,5 +1,8 @@
-//! A simple program that takes a number `n` as input, and writes the `n-1`th and `n`th fibonacci
-//! number as an output.
+//! A program that reads a cook selector tag as its first input, and runs the selected cook over the
+//! rest of the inputs.
+//!
+//! The selector tag is committed as the first byte of the public values, so verifiers know which
+//! computation was proven.
 
 // These two lines are necessary for the program to properly compile.
 //
@@ -7,24 +10,27 @@
 // inside the zkVM.
 #![no_main]
 
-use fibonacci_program::cooks::keccak256::calc_keccak256;
+use fibonacci_program::cooks::{
+    ecdsa_recover::ecdsa_recover, fibonacci::fibonacci, keccak256::calc_keccak256,
+    poseidon_hash::calc_poseidon, verify_ecdsa::verify_ecdsa,
+    verify_p2_proof::verify_plonky2_proof, CookSelector,
+};
 
-// use fibonacci_program::cooks::poseidon_hash::calc_poseidon;
-
-// use fibonacci_program::cooks::verify_ecdsa::verify_ecdsa;
-
-// use fibonacci_program::cooks::ecdsa_recover::ecdsa_recover;
-
-// use fibonacci_program::cooks::verify_p2_proof::verify_plonky2_proof;
-
-// use fibonacci_program::cooks::fibonacci::fibonacci;
 sp1_zkvm::entrypoint!(main);
 
 pub fn main() {
-    //  fibonacci();
-    calc_keccak256();
-    // calc_poseidon();
-    // verify_ecdsa();
-    // ecdsa_recover();
-    // verify_plonky2_proof();
+    let tag = sp1_zkvm::io::read::<u8>();
+    let selector = CookSelector::from_tag(tag).expect("unknown cook selector");
+
+    // Commit to the selected cook before any of its own public values.
+    sp1_zkvm::io::commit_slice(&[tag]);
+
+    match selector {
+        CookSelector::Fibonacci => fibonacci(),
+        CookSelector::Keccak256 => calc_keccak256(),
+        CookSelector::Poseidon => calc_poseidon(),
+        CookSelector::VerifyEcdsa => verify_ecdsa(),
+        CookSelector::EcdsaRecover => ecdsa_recover(),
+        CookSelector::VerifyPlonky2Proof => verify_plonky2_proof(),
+    }
 }
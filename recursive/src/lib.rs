@@ -0,0 +1,129 @@
+use sha2::Digest;
+use sha2::Sha256;
+
+pub fn words_to_bytes_le(words: &[u32; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for i in 0..8 {
+        let word_bytes = words[i].to_le_bytes();
+        bytes[i * 4..(i + 1) * 4].copy_from_slice(&word_bytes);
+    }
+    bytes
+}
+
+/// The leaf of a verified proof: `sha256(vkey || sha256(public_values))`.
+///
+/// Keep in sync with `fibonacci_script::utils::merkle::aggregation_leaf`.
+pub fn leaf_hash(vkey: &[u32; 8], public_values_digest: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(words_to_bytes_le(vkey));
+    hasher.update(public_values_digest);
+    hasher.finalize().into()
+}
+
+/// The digest of the permitted vkeys: `sha256(allowlist[0] || allowlist[1] || ...)`.
+///
+/// Keep in sync with `fibonacci_script::utils::merkle::allowlist_digest`.
+pub fn allowlist_digest(allowlist: &[[u32; 8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for vkey in allowlist.iter() {
+        hasher.update(words_to_bytes_le(vkey));
+    }
+    hasher.finalize().into()
+}
+
+/// Checks the aggregated proofs against the optional allowlist, and returns its digest, zero if
+/// there is none.
+///
+/// The last vkey of the allowlist is the aggregation program's own: a nested aggregate is only
+/// accepted if it committed the same allowlist digest, so it cannot wrap proofs of programs
+/// outside the allowlist. Reordering the allowlist changes its committed digest.
+pub fn check_allowlist(vkeys: &[[u32; 8]], public_values: &[Vec<u8>], allowlist: Option<&[[u32; 8]]>) -> [u8; 32] {
+    let Some(allowlist) = allowlist else {
+        return [0u8; 32];
+    };
+
+    let digest = allowlist_digest(allowlist);
+    let aggregation_vkey = allowlist.last().expect("empty allowlist");
+    for (vkey, public_values) in vkeys.iter().zip(public_values.iter()) {
+        assert!(allowlist.contains(vkey), "vkey is not in the allowlist");
+        if vkey == aggregation_vkey {
+            // a nested aggregate commits `root || allowlist digest`
+            assert!(
+                public_values.len() == 64 && public_values[32..] == digest,
+                "nested aggregate of another allowlist"
+            );
+        }
+    }
+
+    digest
+}
+
+/// A sha256 merkle root over the leaves, padded with zero leaves up to the next power of two.
+///
+/// Nodes are `sha256(left || right)`, the root of no leaves is zero.
+pub fn merkle_root(mut nodes: Vec<[u8; 32]>) -> [u8; 32] {
+    if nodes.is_empty() {
+        return [0u8; 32];
+    }
+    nodes.resize(nodes.len().next_power_of_two(), [0u8; 32]);
+
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                hasher.update(pair[0]);
+                hasher.update(pair[1]);
+                hasher.finalize().into()
+            })
+            .collect();
+    }
+
+    nodes[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_VKEY: [u32; 8] = [1u32; 8];
+    const AGGREGATION_VKEY: [u32; 8] = [2u32; 8];
+
+    fn nested_public_values(allowlist_digest: [u8; 32]) -> Vec<u8> {
+        [[9u8; 32], allowlist_digest].concat()
+    }
+
+    #[test]
+    fn test_nested_aggregate_of_same_allowlist() {
+        let allowlist = [PROGRAM_VKEY, AGGREGATION_VKEY];
+        let digest = allowlist_digest(&allowlist);
+        let committed = check_allowlist(
+            &[PROGRAM_VKEY, AGGREGATION_VKEY],
+            &[vec![0u8; 4], nested_public_values(digest)],
+            Some(&allowlist),
+        );
+        assert_eq!(committed, digest);
+    }
+
+    #[test]
+    #[should_panic(expected = "nested aggregate of another allowlist")]
+    fn test_reject_unrestricted_nested_aggregate() {
+        // a sub-aggregate proven without an allowlist commits a zero digest
+        check_allowlist(
+            &[AGGREGATION_VKEY],
+            &[nested_public_values([0u8; 32])],
+            Some(&[PROGRAM_VKEY, AGGREGATION_VKEY]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "vkey is not in the allowlist")]
+    fn test_reject_vkey_outside_allowlist() {
+        check_allowlist(&[[3u32; 8]], &[vec![]], Some(&[PROGRAM_VKEY, AGGREGATION_VKEY]));
+    }
+
+    #[test]
+    fn test_no_allowlist() {
+        assert_eq!(check_allowlist(&[[3u32; 8]], &[vec![]], None), [0u8; 32]);
+    }
+}
@@ -0,0 +1,375 @@
+//! One entrypoint for proving every cook with the SP1 SDK.
+//!
+//! You can run this script using the following command:
+//! ```shell
+//! RUST_LOG=info cargo run --package fibonacci-script --bin sp1-cook --release -- --mode core keccak256 --n 256
+//! ```
+
+use std::path::PathBuf;
+
+use sp1_p3_type::types::chip_id::P3ChipId;
+use clap::{Parser, Subcommand};
+use fibonacci_script::cooks::{
+    ecdsa, ecrecover, eip712, fibonacci, hybrid, keccak256, p3_verify, poseidon, recursive,
+};
+use fibonacci_script::utils::cook_selector::COOK_ELF;
+use fibonacci_script::utils::fixtures::{CookPublicValues, PROOF_PATH};
+use fibonacci_script::utils::proof_store::ProofStore;
+use fibonacci_script::utils::p2_proof::{parse_circuit_digest, P2Config, P2DataFormat};
+use fibonacci_script::utils::runner::{run_cook, run_cook_checked, ProveMode, RunOptions};
+use sp1_eip712_type::types::sp1_domain::{SP1EIP712Domain, SP1EIP712DomainInput};
+use sp1_sdk::ProverClient;
+use tracing::{info, warn};
+
+/// The EIP-712 omniverse transaction program.
+const EIP712_ELF: &[u8] = include_bytes!("../../../sp1eip712/elf/riscv32im-succinct-zkvm-elf");
+
+/// The plonky2 proof verifying program.
+const P2_ELF: &[u8] = include_bytes!("../../../p2agg/elf/riscv32im-succinct-zkvm-elf");
+
+/// A program that aggregates the proofs of the simple program.
+const AGGREGATION_ELF: &[u8] = include_bytes!("../../../recursive/elf/riscv32im-succinct-zkvm-elf");
+
+/// The plonky3 uni-STARK proof verifying program.
+const P3_AGG_ELF: &[u8] = include_bytes!("../../../p3agg/elf/riscv32im-succinct-zkvm-elf");
+
+/// The arguments for the prove command.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    /// Execute only, or the kind of proof to generate.
+    #[clap(long, value_enum, global = true, default_value_t = ProveMode::Core)]
+    mode: ProveMode,
+
+    /// Execute the program before proving it, to record its cycles and check its public values
+    /// before the proving starts.
+    #[clap(long, global = true)]
+    execute_first: bool,
+
+    #[clap(subcommand)]
+    cook: Cook,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cook {
+    /// Compute the n'th fibonacci number.
+    Fibonacci {
+        #[clap(long, default_value = "20")]
+        n: u32,
+    },
+    /// Hash `n` messages with keccak256.
+    Keccak256 {
+        #[clap(long, default_value = "256")]
+        n: usize,
+    },
+    /// Hash `n` goldilocks vectors with poseidon.
+    Poseidon {
+        #[clap(long, default_value = "32")]
+        n: usize,
+    },
+    /// Verify one secp256k1 signature.
+    Ecdsa,
+    /// Recover the signers of `n` secp256k1 signatures.
+    Ecrecover {
+        #[clap(long, default_value = "2")]
+        n: usize,
+    },
+    /// Verify `n` batches of EIP-712 signed omniverse transactions.
+    Eip712 {
+        #[clap(long, default_value = "16")]
+        n: usize,
+        /// The EIP-712 domain name.
+        #[clap(long, default_value = "Omniverse Transaction")]
+        domain_name: String,
+        /// The EIP-712 domain version.
+        #[clap(long, default_value = "1")]
+        domain_version: String,
+        /// The chain id of the EIP-712 domain.
+        #[clap(long, default_value = "1")]
+        chain_id: u64,
+        /// The verifying contract of the EIP-712 domain, as 0x-prefixed hex.
+        #[clap(long, value_parser = eip712::parse_address, default_value = "0x0000000000000000000000000000000000000000")]
+        verifying_contract: [u8; 20],
+        /// Sign in the domain with this raw 0x-prefixed separator instead of hashing the domain
+        /// fields, e.g. the `EIP712_DOMAIN_HASH` of the earlier fixed domain.
+        #[clap(long, value_parser = eip712::parse_separator, conflicts_with_all = ["domain_name", "domain_version", "chain_id", "verifying_contract"])]
+        domain_separator: Option<[u8; 32]>,
+        /// Commit a validity verdict with every txid instead of aborting on an invalid transaction.
+        #[clap(long)]
+        verdicts: bool,
+        /// Append this many `InvalidTx`s to the batch, which need `--verdicts`.
+        #[clap(long, default_value = "0", requires = "verdicts")]
+        invalid_txs: usize,
+        /// Commit the keccak256 merkle root of the txids and their count instead of every record,
+        /// and save the membership proof of every txid.
+        #[clap(long, conflicts_with = "verdicts")]
+        txid_root: bool,
+    },
+    /// Verify stored plonky2 proofs in one execution.
+    Hybrid {
+        /// The ids of the stored plonky2 proofs.
+        #[clap(long, value_delimiter = ',', default_value = "8")]
+        proof_ids: Vec<String>,
+        /// The hex circuit digests the proofs must be of. Required unless `--mode execute`, where
+        /// the circuits are unchecked with a warning if omitted.
+        #[clap(long, value_delimiter = ',')]
+        circuit_digests: Vec<String>,
+        /// The plonky2 config of the stored proofs.
+        #[clap(long, value_enum, default_value_t = P2Config::Poseidon)]
+        config: P2Config,
+        /// The directory of the stored proofs, `./.p2-data` if omitted.
+        #[clap(long)]
+        data_dir: Option<PathBuf>,
+        /// The encoding of the stored verifier only and common circuit data.
+        #[clap(long, value_enum, default_value_t = P2DataFormat::Plonky2)]
+        format: P2DataFormat,
+        /// Prove a tiny sample circuit for every proof id first, overwriting the stored proofs in
+        /// `--data-dir`, which must be given so the zk-6358 proofs are not overwritten by default.
+        #[clap(long, requires = "data_dir")]
+        sample: bool,
+    },
+    /// Aggregate compressed keccak256 proofs of `n` hashes each, in a tree of the given arity.
+    Recursive {
+        #[clap(long, default_value = "256")]
+        n: usize,
+        /// The number of keccak256 proofs to aggregate.
+        #[clap(long, default_value = "2")]
+        proofs: usize,
+        /// The number of proofs aggregated by one step of the tree.
+        #[clap(long, default_value = "2")]
+        arity: usize,
+    },
+    /// Aggregate compressed keccak256, ecrecover, EIP-712 and plonky2 verifying proofs, only
+    /// accepting the vkeys of those programs.
+    Aggregate {
+        #[clap(long, default_value = "2")]
+        n: usize,
+        /// The number of proofs aggregated by one step of the tree.
+        #[clap(long, default_value = "4")]
+        arity: usize,
+    },
+    /// Verify plonky3 uni-STARK proofs of `n` Poseidon2 permutations each, one per chip.
+    P3Verify {
+        #[clap(long, default_value = "256")]
+        n: usize,
+        /// The chips proving the batches: `poseidon2`, `poseidon2-wide-3` or `poseidon2-wide-7`.
+        #[clap(long, value_delimiter = ',', default_value = "poseidon2")]
+        chips: Vec<P3ChipId>,
+        /// The FRI parameters of the proofs, which the `p3agg` program reads with them.
+        #[clap(long, value_enum, default_value = "compressed")]
+        fri: p3_verify::P3Fri,
+        /// Overrides the log2 of the FRI blowup of `--fri`.
+        #[clap(long)]
+        log_blowup: Option<usize>,
+        /// Overrides the number of FRI queries of `--fri`.
+        #[clap(long)]
+        num_queries: Option<usize>,
+        /// Overrides the proof of work bits of `--fri`.
+        #[clap(long)]
+        pow_bits: Option<usize>,
+        /// Also verify every p3 proof on the host right after proving it.
+        #[clap(long)]
+        self_verify: bool,
+    },
+    /// List the proofs in the proof store.
+    List,
+    /// Re-verify a stored proof against its program.
+    Verify {
+        /// The artifact id, as listed by `list`.
+        id: String,
+    },
+}
+
+/// The ELF a stored proof of `program` was proven with.
+fn program_elf(program: &str) -> Option<&'static [u8]> {
+    match program {
+        "fibonacci" | "keccak256" | "poseidon" | "ecdsa" | "ecrecover" => Some(COOK_ELF),
+        "eip712" => Some(EIP712_ELF),
+        "hybrid" => Some(P2_ELF),
+        "recursive" | "aggregate" => Some(AGGREGATION_ELF),
+        "p3-agg" => Some(P3_AGG_ELF),
+        _ => None,
+    }
+}
+
+fn main() {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+
+    // Parse the command line arguments.
+    let cli = Cli::parse();
+
+    // Setup the prover client.
+    let client = ProverClient::new();
+    let run = RunOptions { mode: cli.mode, execute_first: cli.execute_first };
+
+    match cli.cook {
+        Cook::Fibonacci { n } => {
+            info!("n: {}", n);
+            let (output, _) = run_cook::<fibonacci::FibonacciPublicValues>(
+                &client,
+                "fibonacci",
+                COOK_ELF,
+                fibonacci::build_stdin(n),
+                run,
+            );
+            let (_, _, fib_n) = fibonacci::decode_public_values(output.public_values().as_slice());
+            info!("fib(n): {}", fib_n);
+        }
+        Cook::Keccak256 { n } => {
+            info!("n: {}", n);
+            let (output, _) = run_cook::<keccak256::Keccak256PublicValues>(&client, "keccak256", COOK_ELF, keccak256::build_stdin(n), run);
+            info!("hash number: (n): {}", keccak256::hash_count(output.public_values().as_slice()));
+        }
+        Cook::Poseidon { n } => {
+            info!("n: {}", n);
+            let (output, _) = run_cook::<poseidon::PoseidonPublicValues>(&client, "poseidon", COOK_ELF, poseidon::build_stdin(n), run);
+            info!("hash number: (n): {}", poseidon::hash_count(output.public_values().as_slice()));
+        }
+        Cook::Ecdsa => {
+            let (output, _) = run_cook::<ecdsa::EcdsaPublicValues>(&client, "ecdsa", COOK_ELF, ecdsa::build_stdin(), run);
+            let committed = ecdsa::EcdsaPublicValues::decode(output.public_values().as_slice());
+            info!("verified signature of {}", committed.verified.public_key);
+        }
+        Cook::Ecrecover { n } => {
+            let (output, _) = run_cook::<ecrecover::EcrecoverPublicValues>(&client, "ecrecover", COOK_ELF, ecrecover::build_stdin(n), run);
+            let committed = ecrecover::EcrecoverPublicValues::decode(output.public_values().as_slice());
+            info!("recovered {} signers", committed.recovered.len());
+        }
+        Cook::Eip712 { n, domain_name, domain_version, chain_id, verifying_contract, domain_separator, verdicts, invalid_txs, txid_root } => {
+            let domain = match domain_separator {
+                Some(separator) => SP1EIP712DomainInput::Separator(separator),
+                None => SP1EIP712DomainInput::Domain(SP1EIP712Domain {
+                    name: domain_name,
+                    version: domain_version,
+                    chain_id,
+                    verifying_contract,
+                }),
+            };
+            info!("eip712 domain separator: 0x{}", hex::encode(domain.separator()));
+            let txs = eip712::generate_txs(n, &domain, invalid_txs);
+            let (output, _) = run_cook::<eip712::Eip712PublicValues>(&client, "eip712", EIP712_ELF, eip712::build_stdin(&txs, &domain, verdicts, txid_root), run);
+            let committed = eip712::Eip712PublicValues::decode(output.public_values().as_slice());
+            if verdicts {
+                info!("{} of {} transactions invalid", committed.num_invalid(), committed.records.len());
+            }
+            if txid_root {
+                eip712::save_txid_proofs("eip712", &txs, &committed);
+                info!("txid root of {} transactions: {}", committed.count, committed.txid_root.as_deref().unwrap_or_default());
+            }
+        }
+        Cook::Hybrid { proof_ids, circuit_digests, config, data_dir, format, sample } => {
+            let expected_circuits = (!circuit_digests.is_empty()).then(|| {
+                circuit_digests
+                    .iter()
+                    .map(|digest| parse_circuit_digest(digest).expect("invalid circuit digest"))
+                    .collect::<Vec<_>>()
+            });
+            if expected_circuits.is_none() {
+                // every proving mode saves a fixture, which must not be of unchecked circuits
+                assert_eq!(run.mode, ProveMode::Execute, "--circuit-digests is required to prove the plonky2 proofs");
+                warn!("no --circuit-digests given, the circuits of the proofs are not checked");
+            }
+
+            let proofs = hybrid::stored_proofs(data_dir.as_deref(), &proof_ids, format);
+            if sample {
+                hybrid::generate_sample_proofs(config, &proofs);
+            }
+
+            let input = hybrid::load_input(config, &proofs);
+            let (output, _) = run_cook_checked::<hybrid::HybridPublicValues>(
+                &client,
+                "hybrid",
+                P2_ELF,
+                input.stdin,
+                run,
+                |public_values| {
+                    if let Some(expected_circuits) = &expected_circuits {
+                        hybrid::check_circuit_digests(public_values, expected_circuits)?;
+                    }
+                    hybrid::check_common_data(public_values, &input.expected)
+                },
+            );
+
+            let committed = hybrid::HybridPublicValues::decode(output.public_values().as_slice());
+            assert_eq!(committed, input.expected, "committed plonky2 proof digests mismatch");
+            info!("verified {} plonky2 proofs of {} circuits", committed.proofs.len(), input.circuit_digests.len());
+        }
+        Cook::Recursive { n, proofs, arity } => {
+            let inputs = recursive::prove_keccak_inputs(&client, proofs, n);
+
+            // The intermediate levels are always compressed, only the final step follows `--mode`.
+            let (inputs, levels) = recursive::aggregate_levels(&client, AGGREGATION_ELF, inputs, arity, None);
+            let (output, _) = tracing::info_span!("aggregate the proofs").in_scope(|| {
+                run_cook::<recursive::AggregationPublicValues>(&client, "recursive", AGGREGATION_ELF, recursive::build_stdin(inputs, None), run)
+            });
+            recursive::save_inclusion_proofs("recursive", &levels, output.public_values().as_slice());
+            info!("merkle root: 0x{}", hex::encode(levels.root()));
+        }
+        Cook::Aggregate { n, arity } => {
+            let p2_input = hybrid::load_input(
+                P2Config::Poseidon,
+                &hybrid::stored_proofs(None, &["8".to_string()], P2DataFormat::Plonky2),
+            );
+            let inputs = recursive::prove_program_inputs(
+                &client,
+                vec![
+                    (COOK_ELF, keccak256::build_stdin(n)),
+                    (COOK_ELF, ecrecover::build_stdin(n)),
+                    (EIP712_ELF, eip712::build_stdin(&eip712::generate_txs(n, &eip712::default_domain(), 0), &eip712::default_domain(), false, false)),
+                    (P2_ELF, p2_input.stdin),
+                ],
+            );
+            let permitted = recursive::input_vkeys(&inputs);
+
+            let (inputs, levels) =
+                recursive::aggregate_levels(&client, AGGREGATION_ELF, inputs, arity, Some(permitted));
+            let allowlist = levels.allowlist.clone();
+            let (output, _) = tracing::info_span!("aggregate the proofs").in_scope(|| {
+                run_cook::<recursive::AggregationPublicValues>(&client, "aggregate", AGGREGATION_ELF, recursive::build_stdin(inputs, allowlist.as_deref()), run)
+            });
+            recursive::save_inclusion_proofs("aggregate", &levels, output.public_values().as_slice());
+            info!("merkle root: 0x{}", hex::encode(levels.root()));
+            info!("allowlist digest: 0x{}", hex::encode(levels.allowlist_digest()));
+        }
+        Cook::P3Verify { n, chips, fri, log_blowup, num_queries, pow_bits, self_verify } => {
+            let fri = fri.params(log_blowup, num_queries, pow_bits);
+            info!("p3 fri params: {:?}", fri);
+            let batches = p3_verify::prove_batches(n, &chips, fri, self_verify);
+            for batch in &batches {
+                info!("p3 {} proof: {:?}", batch.chip, batch.metrics);
+            }
+            let (output, _) = run_cook::<p3_verify::P3PublicValues>(
+                &client,
+                "p3-agg",
+                P3_AGG_ELF,
+                p3_verify::build_stdin(&batches),
+                run,
+            );
+
+            let committed = p3_verify::P3PublicValues::decode(output.public_values().as_slice());
+            assert_eq!(committed, p3_verify::P3PublicValues::expected(&batches), "committed p3 digests mismatch");
+            info!("verified {} p3 proofs", batches.len());
+        }
+        Cook::List => {
+            let store = ProofStore::open(PROOF_PATH).expect("open proof store error");
+            for entry in store.list() {
+                println!(
+                    "{}\t{}\t{}\tcycles: {:?}\tvk: {}",
+                    entry.id,
+                    entry.program,
+                    entry.kind.as_str(),
+                    entry.cycles,
+                    entry.vk_hash
+                );
+            }
+        }
+        Cook::Verify { id } => {
+            let store = ProofStore::open(PROOF_PATH).expect("open proof store error");
+            let entry = store.get(&id).expect("no artifact with the id");
+            let elf = program_elf(&entry.program).expect("unknown program");
+            store.verify(&client, &id, elf).expect("failed to verify stored proof");
+            info!("successfully verified {}", id);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::utils::cook_selector::CookSelector;
+use crate::utils::fixtures::CookPublicValues;
+
+/// The length of the committed `keccak256(message) || uncompressed public key`.
+pub const SIGNED_DIGEST_LEN: usize = 32 + 65;
+
+/// Signs a message with a random key, and writes the message, public key and signature.
+pub fn build_stdin() -> SP1Stdin {
+    // prepare message signature
+    let mut rng = rand::thread_rng();
+    let sign_key = SigningKey::random(&mut rng);
+    let message = "hello omniverse".as_bytes();
+
+    let mut hasher = Keccak::v256();
+    hasher.update(message);
+    let mut msg_digest = [0u8; 32];
+    hasher.finalize(&mut msg_digest);
+
+    let signature: Signature = sign_key.sign_prehash(&msg_digest).unwrap();
+    let signature_vu8 = signature.to_bytes();
+
+    let verify_key = VerifyingKey::from(sign_key);
+    let pk_vu8 = verify_key.to_encoded_point(false).to_bytes();
+
+    assert!(verify_key.verify_prehash(&msg_digest, &signature).is_ok(), "executing verification fialed!");
+
+    let mut sp1in = CookSelector::VerifyEcdsa.new_stdin();
+    sp1in.write_vec(message.to_vec());
+    sp1in.write_vec(pk_vu8.to_vec());
+    sp1in.write_vec(signature_vu8.to_vec());
+
+    sp1in
+}
+
+/// The keccak256 digest of a signed message and the uncompressed public key of its signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedDigest {
+    pub message_digest: String,
+    pub public_key: String,
+}
+
+impl SignedDigest {
+    /// Decodes one committed `keccak256(message) || public key`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), SIGNED_DIGEST_LEN, "invalid signed digest");
+        let (message_digest, public_key) = bytes.split_at(32);
+        SignedDigest {
+            message_digest: format!("0x{}", hex::encode(message_digest)),
+            public_key: format!("0x{}", hex::encode(public_key)),
+        }
+    }
+}
+
+/// The committed digest of the verified message and the public key it was verified against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcdsaPublicValues {
+    pub verified: SignedDigest,
+}
+
+impl CookPublicValues for EcdsaPublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (_, pis) = CookSelector::split_public_values(public_values);
+        EcdsaPublicValues {
+            verified: SignedDigest::from_bytes(pis),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_public_values() {
+        let pis = [vec![CookSelector::VerifyEcdsa.tag()], vec![1u8; 32], vec![4u8; 65]].concat();
+        let decoded = EcdsaPublicValues::decode(&pis);
+        assert_eq!(decoded.verified.message_digest, format!("0x{}", "01".repeat(32)));
+        assert_eq!(decoded.verified.public_key, format!("0x{}", "04".repeat(65)));
+    }
+}
@@ -0,0 +1,61 @@
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use k256::elliptic_curve::generic_array::sequence::Lengthen;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::cooks::ecdsa::{SignedDigest, SIGNED_DIGEST_LEN};
+use crate::utils::cook_selector::CookSelector;
+use crate::utils::fixtures::CookPublicValues;
+
+/// Signs `n` messages with one random key, and writes each message, the public key and the
+/// recoverable signature.
+pub fn build_stdin(n: usize) -> SP1Stdin {
+    // prepare message signature
+    let mut rng = rand::thread_rng();
+    let sign_key = SigningKey::random(&mut rng);
+    let verify_key = VerifyingKey::from(sign_key.clone());
+    let pk_vu8 = verify_key.to_encoded_point(false).to_bytes();
+
+    let mut sp1in = CookSelector::EcdsaRecover.new_stdin();
+    sp1in.write(&n);
+
+    for i in 0..n {
+        let message = format!("hello omniverse {i}").as_bytes().to_vec();
+
+        let mut hasher = Keccak::v256();
+        hasher.update(&message);
+        let mut msg_digest = [0u8; 32];
+        hasher.finalize(&mut msg_digest);
+
+        let signature = sign_key.sign_prehash_recoverable(&msg_digest).unwrap();
+        // recoverable is 65 bytes
+        let signature_vu8 = signature.0.to_bytes().append(signature.1.to_byte());
+
+        assert!(verify_key.verify_prehash(&msg_digest, &signature.0).is_ok(), "executing verification fialed!");
+
+        sp1in.write_vec(message);
+        sp1in.write_vec(pk_vu8.to_vec());
+        sp1in.write_vec(signature_vu8.to_vec());
+    }
+
+    sp1in
+}
+
+/// The committed digest of every message and the public key recovered from its signature, in the
+/// order of the inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcrecoverPublicValues {
+    pub recovered: Vec<SignedDigest>,
+}
+
+impl CookPublicValues for EcrecoverPublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (_, pis) = CookSelector::split_public_values(public_values);
+        assert!(pis.len() % SIGNED_DIGEST_LEN == 0, "invalid recovered signers");
+        EcrecoverPublicValues {
+            recovered: pis.chunks(SIGNED_DIGEST_LEN).map(SignedDigest::from_bytes).collect(),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use alloy_sol_types::{sol, SolType};
+use plonky2_ecdsa::curve::secp256k1::Secp256K1;
+use plonky2_ecdsa::curve::{curve_types::{AffinePoint, Curve, CurveScalar}, ecdsa::{ECDSAPublicKey, ECDSASecretKey}};
+use plonky2_field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2_field::types::Sample;
+use sp1_eip712_type::types::{sp1_domain::{SP1EIP712Domain, SP1EIP712DomainInput}, sp1_tx_types::{SP1SignedOmniverseTx, SP1TxVerdict}};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+use zk_6358::{prelude::ZK6358GoldilocksField, utils6358::{type_utils::ZK6358DataHashing, utxo::HASH_LEN}};
+
+use crate::utils::fixtures::{CookPublicValues, FIXTURE_PATH};
+use crate::utils::merkle::{MerkleHash, MerkleProof, MerkleTree};
+use crate::utils::unit_tests::sp1_test_generate_a_batch;
+
+/// The domain the omniverse transactions are signed in unless another one is given.
+pub fn default_domain() -> SP1EIP712DomainInput {
+    SP1EIP712DomainInput::Domain(SP1EIP712Domain {
+        name: "Omniverse Transaction".to_string(),
+        version: "1".to_string(),
+        chain_id: 1,
+        verifying_contract: [0u8; 20],
+    })
+}
+
+/// Parses a `0x`-prefixed 20-byte contract address.
+pub fn parse_address(s: &str) -> Result<[u8; 20], String> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    bytes.try_into().map_err(|_| format!("not a 20-byte address: {}", s))
+}
+
+/// Parses a `0x`-prefixed 32-byte domain separator.
+pub fn parse_separator(s: &str) -> Result<[u8; HASH_LEN], String> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    bytes.try_into().map_err(|_| format!("not a 32-byte domain separator: {}", s))
+}
+
+/// Generates `n` batches of omniverse transactions, signed in `domain` with one random key.
+pub fn generate_batches(n: usize, domain: &SP1EIP712DomainInput) -> Vec<SP1SignedOmniverseTx> {
+    type EC = Secp256K1;
+
+    let sk = ECDSASecretKey::<EC>(Secp256K1Scalar::rand());
+    let pk = ECDSAPublicKey((CurveScalar(sk.0) * EC::GENERATOR_PROJECTIVE).to_affine());
+    let AffinePoint { x, y, .. } = pk.0;
+    let mut x_le_bytes = Vec::new();
+    x.0.iter().for_each(|i| {
+        x_le_bytes.append(&mut i.to_le_bytes().to_vec());
+    });
+    x_le_bytes.reverse();
+
+    let mut y_le_bytes = Vec::new();
+    y.0.iter().for_each(|i| {
+        y_le_bytes.append(&mut i.to_le_bytes().to_vec());
+    });
+    y_le_bytes.reverse();
+
+    let domain_separator = domain.separator();
+    let mut batched_somtx_vec = Vec::new();
+    (0..n).for_each(|_| {
+        batched_somtx_vec.append(&mut sp1_test_generate_a_batch(sk, x_le_bytes.clone().try_into().unwrap(), y_le_bytes.clone().try_into().unwrap(), &domain_separator));
+    });
+
+    batched_somtx_vec
+}
+
+/// `n` batches of transactions signed in `domain`, followed by `invalid_txs` `InvalidTx`s.
+pub fn generate_txs(n: usize, domain: &SP1EIP712DomainInput, invalid_txs: usize) -> Vec<SP1SignedOmniverseTx> {
+    let mut batched_somtx_vec = generate_batches(n, domain);
+    batched_somtx_vec.extend((0..invalid_txs).map(|_| SP1SignedOmniverseTx::InvalidTx));
+    batched_somtx_vec
+}
+
+/// Writes `domain`, whether the guest commits verdicts and whether it commits the txid merkle root
+/// instead of the records, then the count and list of `batched_somtx_vec`.
+pub fn build_stdin(
+    batched_somtx_vec: &[SP1SignedOmniverseTx],
+    domain: &SP1EIP712DomainInput,
+    with_verdicts: bool,
+    with_txid_root: bool,
+) -> SP1Stdin {
+    let mut sp1in = SP1Stdin::new();
+    sp1in.write(domain);
+    sp1in.write::<bool>(&with_verdicts);
+    sp1in.write::<bool>(&with_txid_root);
+    sp1in.write::<usize>(&batched_somtx_vec.len());
+    batched_somtx_vec.iter().for_each(|somtx| {
+        sp1in.write(somtx);
+    });
+
+    sp1in
+}
+
+/// The record of one transaction: `(txid, EIP-712 digest, signer, verdict)`.
+pub type TxRecordTuple = sol! {
+    tuple(bytes32, bytes32, address, uint8)
+};
+
+/// The public values committed by the `sp1eip712` program, `abi.encode(bytes32 domainSeparator,
+/// bool withVerdicts, bytes32 txidRoot, uint256 count, (bytes32, bytes32, address, uint8)[] records)`.
+///
+/// Keep in sync with `sp1eip712::eip::tx_record::PublicValuesTuple`.
+pub type PublicValuesTuple = sol! {
+    tuple(bytes32, bool, bytes32, uint256, (bytes32, bytes32, address, uint8)[])
+};
+
+/// One committed transaction: its txid, the EIP-712 digest it is signed over, the Ethereum address
+/// of the recovered signer and its verdict.
+///
+/// The txid and digest of an `InvalidTx` are zero, as is the signer when none can be recovered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip712TxRecord {
+    pub txid: String,
+    pub digest: String,
+    pub signer: String,
+    pub verdict: String,
+}
+
+impl Eip712TxRecord {
+    fn from_tuple((txid, digest, signer, verdict): <TxRecordTuple as SolType>::RustType) -> Self {
+        Eip712TxRecord {
+            txid: format!("0x{}", hex::encode(txid)),
+            digest: format!("0x{}", hex::encode(digest)),
+            signer: signer.to_checksum(None),
+            verdict: SP1TxVerdict::from_tag(verdict).expect("unknown eip712 verdict").as_str().to_string(),
+        }
+    }
+}
+
+/// The committed domain separator, then either the record of every transaction, in order, or the
+/// merkle root of their txids.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eip712PublicValues {
+    pub domain_separator: String,
+    /// Whether invalid transactions were committed with their verdict instead of aborting.
+    pub with_verdicts: bool,
+    /// The keccak256 merkle root of the txids, whose records are then left out.
+    pub txid_root: Option<String>,
+    pub count: usize,
+    pub records: Vec<Eip712TxRecord>,
+}
+
+impl Eip712PublicValues {
+    /// The number of transactions with any other verdict than `valid`.
+    pub fn num_invalid(&self) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.verdict.as_str() != SP1TxVerdict::Valid.as_str())
+            .count()
+    }
+}
+
+impl CookPublicValues for Eip712PublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (domain_separator, with_verdicts, txid_root, count, records) =
+            PublicValuesTuple::abi_decode_params(public_values, false).expect("decode eip712 public values error");
+        Eip712PublicValues {
+            domain_separator: format!("0x{}", hex::encode(domain_separator)),
+            with_verdicts,
+            txid_root: (!txid_root.is_zero()).then(|| format!("0x{}", hex::encode(txid_root))),
+            count: count.to::<usize>(),
+            records: records.into_iter().map(Eip712TxRecord::from_tuple).collect(),
+        }
+    }
+}
+
+/// The txid of `tx`, as hashed by the `sp1eip712` program, or zero for an `InvalidTx`.
+pub fn txid(tx: &SP1SignedOmniverseTx) -> [u8; HASH_LEN] {
+    type F = ZK6358GoldilocksField;
+    match tx {
+        SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) => {
+            <_ as ZK6358DataHashing<F>>::hash_keccak256(signed_deploy_tx.borrow_deploy_tx())
+        }
+        SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => {
+            <_ as ZK6358DataHashing<F>>::hash_keccak256(signed_mint_tx.borrow_mint_tx())
+        }
+        SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => {
+            <_ as ZK6358DataHashing<F>>::hash_keccak256(signed_spend_tx.borrow_spend_tx())
+        }
+        SP1SignedOmniverseTx::InvalidTx => [0u8; HASH_LEN],
+    }
+}
+
+/// The keccak256 merkle tree over the txids of `txs`, whose root the `sp1eip712` program commits.
+pub fn txid_tree(txs: &[SP1SignedOmniverseTx]) -> MerkleTree {
+    MerkleTree::with_hash(txs.iter().map(txid).collect(), MerkleHash::Keccak256)
+}
+
+/// The committed txid root and count, and the membership proof of every txid, as read by
+/// `TxidMerkleTest` in Foundry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxidProofs {
+    pub root: String,
+    pub count: usize,
+    pub proofs: Vec<MerkleProof>,
+}
+
+/// Checks the committed txid root against `txs`, then saves the membership proof of every txid to
+/// `{FIXTURE_PATH}{name}-txid-proofs.json`, for `TxidMerkle.verify` in Solidity.
+pub fn save_txid_proofs(name: &str, txs: &[SP1SignedOmniverseTx], public_values: &Eip712PublicValues) {
+    let tree = txid_tree(txs);
+    assert_eq!(public_values.count, tree.leaf_count(), "committed txid count mismatch");
+    assert_eq!(
+        public_values.txid_root,
+        Some(format!("0x{}", hex::encode(tree.root()))),
+        "committed txid root mismatch"
+    );
+
+    let proofs = TxidProofs {
+        root: format!("0x{}", hex::encode(tree.root())),
+        count: tree.leaf_count(),
+        proofs: (0..tree.leaf_count()).map(|i| tree.proof(i)).collect(),
+    };
+
+    std::fs::create_dir_all(FIXTURE_PATH).expect("failed to create fixture path");
+    std::fs::write(
+        format!("{}{}-txid-proofs.json", FIXTURE_PATH, name),
+        serde_json::to_string_pretty(&proofs).unwrap(),
+    )
+    .expect("failed to write txid proofs");
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_decode_public_values() {
+        let signer = Address::repeat_byte(0x11);
+        let records = vec![
+            (B256::repeat_byte(1), B256::repeat_byte(2), signer, SP1TxVerdict::Valid.tag()),
+            (B256::ZERO, B256::ZERO, Address::ZERO, SP1TxVerdict::InvalidVariant.tag()),
+        ];
+        let encoded =
+            PublicValuesTuple::abi_encode_params(&(B256::repeat_byte(9), true, B256::ZERO, U256::from(2), records));
+
+        let decoded = Eip712PublicValues::decode(&encoded);
+        assert_eq!(decoded.domain_separator, format!("0x{}", "09".repeat(32)));
+        assert!(decoded.with_verdicts);
+        assert_eq!(decoded.txid_root, None);
+        assert_eq!(decoded.count, 2);
+        assert_eq!(decoded.records.len(), 2);
+        assert_eq!(decoded.records[0].txid, format!("0x{}", "01".repeat(32)));
+        assert_eq!(decoded.records[0].signer, signer.to_checksum(None));
+        assert_eq!(decoded.records[1].verdict, "invalid-variant");
+        assert_eq!(decoded.num_invalid(), 1);
+    }
+
+    #[test]
+    fn test_decode_txid_root() {
+        let txs = generate_txs(1, &default_domain(), 1);
+        let tree = txid_tree(&txs);
+        let encoded = PublicValuesTuple::abi_encode_params(&(
+            B256::repeat_byte(9),
+            false,
+            B256::from(tree.root()),
+            U256::from(txs.len()),
+            vec![],
+        ));
+
+        let public_values = Eip712PublicValues::decode(&encoded);
+        assert_eq!(public_values.txid_root, Some(format!("0x{}", hex::encode(tree.root()))));
+        assert_eq!(public_values.count, txs.len());
+        assert!(public_values.records.is_empty());
+
+        // the invalid transaction is committed with a zero txid
+        assert_eq!(txid(txs.last().unwrap()), [0u8; HASH_LEN]);
+        (0..tree.leaf_count()).for_each(|i| assert!(tree.proof(i).verify(&tree.root())));
+    }
+}
@@ -0,0 +1,38 @@
+use alloy_sol_types::{sol, SolType};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+
+use crate::utils::cook_selector::CookSelector;
+use crate::utils::fixtures::CookPublicValues;
+
+/// The public values encoded as a tuple that can be easily deserialized inside Solidity.
+pub type PublicValuesTuple = sol! {
+    tuple(uint32, uint32, uint32)
+};
+
+pub fn build_stdin(n: u32) -> SP1Stdin {
+    let mut stdin = CookSelector::Fibonacci.new_stdin();
+    stdin.write(&n);
+    stdin
+}
+
+/// Decodes `(n, a, b)` from the public values committed by the cook program.
+pub fn decode_public_values(public_values: &[u8]) -> (u32, u32, u32) {
+    let (_, bytes) = CookSelector::split_public_values(public_values);
+    PublicValuesTuple::abi_decode(bytes, false).unwrap()
+}
+
+/// The decoded public values in the fibonacci fixture, which the Foundry test checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FibonacciPublicValues {
+    pub n: u32,
+    pub a: u32,
+    pub b: u32,
+}
+
+impl CookPublicValues for FibonacciPublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (n, a, b) = decode_public_values(public_values);
+        FibonacciPublicValues { n, a, b }
+    }
+}
@@ -0,0 +1,238 @@
+use std::path::Path;
+
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::plonk::{circuit_data::VerifierOnlyCircuitData, config::{GenericConfig, Hasher, KeccakGoldilocksConfig, PoseidonGoldilocksConfig}, proof::ProofWithPublicInputs};
+use plonky2_field::goldilocks_field::GoldilocksField;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+use tiny_keccak::{Hasher as _, Keccak};
+
+use crate::utils::error::{CookError, CookResult};
+use crate::utils::fixtures::CookPublicValues;
+use crate::utils::p2_proof::{
+    circuit_digest_hex, generate_sample_proof, hash_elements, P2CircuitCache, P2Config, P2DataFormat, P2ProofPaths,
+    FRI_PROOF_DIR,
+};
+
+const P2D: usize = 2;
+type P2F = GoldilocksField;
+
+/// The paths of the stored plonky2 proofs `proof_ids` in `dir`, `FRI_PROOF_DIR` if `None`.
+pub fn stored_proofs(dir: Option<&Path>, proof_ids: &[String], format: P2DataFormat) -> Vec<P2ProofPaths> {
+    let dir = dir.unwrap_or(Path::new(FRI_PROOF_DIR));
+    proof_ids
+        .iter()
+        .map(|id| P2ProofPaths::in_dir_with_format(dir, id, format))
+        .collect()
+}
+
+/// Proves the plonky2 sample circuit of the given `config` once for every path in `proofs`, each
+/// with a different witness.
+pub fn generate_sample_proofs(config: P2Config, proofs: &[P2ProofPaths]) {
+    proofs.iter().enumerate().for_each(|(i, paths)| {
+        match config {
+            P2Config::Poseidon => generate_sample_proof::<P2F, PoseidonGoldilocksConfig, P2D>(paths, i as u64),
+            P2Config::Keccak => generate_sample_proof::<P2F, KeccakGoldilocksConfig, P2D>(paths, i as u64),
+        }
+        .expect("generate sample p2 proof error");
+    });
+}
+
+/// Loads and verifies the stored plonky2 proofs, grouped by circuit.
+pub fn load_circuits<C: GenericConfig<P2D, F = P2F>>(proofs: &[P2ProofPaths]) -> P2CircuitCache<P2F, C, P2D> {
+    let mut cache = P2CircuitCache::new();
+    proofs.iter().for_each(|paths| {
+        cache.add(paths).expect("load stored p2 proof error");
+    });
+    cache
+}
+
+/// Writes the `config` tag, the count of proofs and the count of circuits, then for every circuit
+/// its digest, verifier data and proofs, each after its index in the inputs, for the `p2agg`
+/// program.
+pub fn build_stdin<C: GenericConfig<P2D, F = P2F>>(config: P2Config, cache: &P2CircuitCache<P2F, C, P2D>) -> SP1Stdin {
+    let mut sp1in = SP1Stdin::new();
+    sp1in.write::<u8>(&config.tag());
+    sp1in.write::<usize>(&cache.proof_count());
+    sp1in.write::<usize>(&cache.circuits().len());
+
+    cache.circuits().iter().for_each(|circuit| {
+        sp1in.write::<[u64; 4]>(&circuit.circuit_digest);
+        sp1in.write::<VerifierOnlyCircuitData<C, P2D>>(&circuit.verifier_data.verifier_only);
+        sp1in.write_vec(circuit.common_bytes.clone());
+
+        sp1in.write::<usize>(&circuit.proofs.len());
+        circuit.proofs.iter().for_each(|(index, ppis)| {
+            sp1in.write::<usize>(index);
+            sp1in.write::<ProofWithPublicInputs<P2F, C, P2D>>(ppis);
+        });
+    });
+
+    sp1in
+}
+
+/// `keccak256` of the gate-serialized common circuit data, as committed by the `p2agg` program.
+pub fn common_data_hash(common_bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(common_bytes);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// The digests of every proof in `cache`, in the order they were added, as committed by the
+/// `p2agg` program.
+pub fn expected_digests<C: GenericConfig<P2D, F = P2F>>(cache: &P2CircuitCache<P2F, C, P2D>) -> Vec<P2ProofDigest> {
+    let mut digests = cache
+        .circuits()
+        .iter()
+        .flat_map(|circuit| {
+            let constants_cap_hash = hash_elements(PoseidonHash::hash_no_pad(
+                &circuit.verifier_data.verifier_only.constants_sigmas_cap.flatten(),
+            ));
+            let common_data_hash = common_data_hash(&circuit.common_bytes);
+            circuit.proofs.iter().map(move |(index, ppis)| {
+                (
+                    *index,
+                    P2ProofDigest {
+                        circuit_digest: circuit.circuit_digest,
+                        constants_cap_hash,
+                        common_data_hash,
+                        public_inputs_hash: hash_elements(ppis.get_public_inputs_hash()),
+                    },
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    digests.sort_by_key(|(index, _)| *index);
+
+    digests.into_iter().map(|(_, digest)| digest).collect()
+}
+
+/// The `p2agg` input of some stored plonky2 proofs, with what it is expected to commit.
+pub struct HybridInput {
+    pub stdin: SP1Stdin,
+    pub circuit_digests: Vec<[u64; 4]>,
+    pub expected: HybridPublicValues,
+}
+
+/// Loads the stored plonky2 proofs of the given `config` and builds the `p2agg` input.
+pub fn load_input(config: P2Config, proofs: &[P2ProofPaths]) -> HybridInput {
+    fn load_with<C: GenericConfig<P2D, F = P2F>>(config: P2Config, proofs: &[P2ProofPaths]) -> HybridInput {
+        let cache = load_circuits::<C>(proofs);
+        HybridInput {
+            stdin: build_stdin(config, &cache),
+            circuit_digests: cache.circuits().iter().map(|circuit| circuit.circuit_digest).collect(),
+            expected: HybridPublicValues {
+                config,
+                proofs: expected_digests(&cache),
+            },
+        }
+    }
+
+    match config {
+        P2Config::Poseidon => load_with::<PoseidonGoldilocksConfig>(config, proofs),
+        P2Config::Keccak => load_with::<KeccakGoldilocksConfig>(config, proofs),
+    }
+}
+
+/// The digests of one verified plonky2 proof, as canonical goldilocks elements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P2ProofDigest {
+    pub circuit_digest: [u64; 4],
+    /// `PoseidonHash` of the flattened constants sigmas cap of the circuit
+    pub constants_cap_hash: [u64; 4],
+    /// `common_data_hash` of the common circuit data the proof was verified with
+    pub common_data_hash: [u8; 32],
+    pub public_inputs_hash: [u64; 4],
+}
+
+/// The config and the digests of every verified plonky2 proof, in the order of `--proof-ids`,
+/// though the proofs are verified grouped by circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HybridPublicValues {
+    pub config: P2Config,
+    pub proofs: Vec<P2ProofDigest>,
+}
+
+impl CookPublicValues for HybridPublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (tag, digests): (u8, Vec<([u64; 4], [u64; 4], [u8; 32], [u64; 4])>) =
+            bincode::deserialize(public_values).expect("decode plonky2 proof digests error");
+        HybridPublicValues {
+            config: P2Config::from_tag(tag).expect("unknown plonky2 config"),
+            proofs: digests
+                .into_iter()
+                .map(|(circuit_digest, constants_cap_hash, common_data_hash, public_inputs_hash)| P2ProofDigest {
+                    circuit_digest,
+                    constants_cap_hash,
+                    common_data_hash,
+                    public_inputs_hash,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Checks that every proof committed in `public_values` is of one of the `expected` circuits.
+pub fn check_circuit_digests(public_values: &HybridPublicValues, expected: &[[u64; 4]]) -> CookResult<()> {
+    for proof in public_values.proofs.iter() {
+        if !expected.contains(&proof.circuit_digest) {
+            return Err(CookError::VkMismatch {
+                expected: expected.iter().map(circuit_digest_hex).collect::<Vec<_>>().join(","),
+                found: circuit_digest_hex(&proof.circuit_digest),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every proof committed in `public_values` was verified with the common circuit data
+/// the host loaded for it, which the circuit digest does not cover.
+pub fn check_common_data(public_values: &HybridPublicValues, expected: &HybridPublicValues) -> CookResult<()> {
+    if public_values.proofs.len() != expected.proofs.len() {
+        return Err(CookError::Verification(format!(
+            "{} plonky2 proofs committed, {} expected",
+            public_values.proofs.len(),
+            expected.proofs.len()
+        )));
+    }
+
+    for (proof, expected) in public_values.proofs.iter().zip(expected.proofs.iter()) {
+        if proof.common_data_hash != expected.common_data_hash {
+            return Err(CookError::VkMismatch {
+                expected: format!("0x{}", hex::encode(expected.common_data_hash)),
+                found: format!("0x{}", hex::encode(proof.common_data_hash)),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public_values(common_data_hash: [u8; 32]) -> HybridPublicValues {
+        HybridPublicValues {
+            config: P2Config::Poseidon,
+            proofs: vec![P2ProofDigest {
+                circuit_digest: [1; 4],
+                constants_cap_hash: [2; 4],
+                common_data_hash,
+                public_inputs_hash: [3; 4],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_check_common_data() {
+        let expected = public_values(common_data_hash(b"gates"));
+        assert!(check_common_data(&expected, &expected).is_ok());
+
+        // same circuit digest, other gates
+        let committed = public_values(common_data_hash(b"other gates"));
+        assert!(matches!(check_common_data(&committed, &expected), Err(CookError::VkMismatch { .. })));
+    }
+}
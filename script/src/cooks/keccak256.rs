@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+
+use crate::utils::cook_selector::CookSelector;
+use crate::utils::fixtures::CookPublicValues;
+
+pub fn build_stdin(n: usize) -> SP1Stdin {
+    build_stdin_with_step(n, 1)
+}
+
+/// `build_stdin`, with the `i`-th message numbered `i * step`, so the proofs of different steps
+/// hash different messages.
+pub fn build_stdin_with_step(n: usize, step: usize) -> SP1Stdin {
+    let mut sp1in = CookSelector::Keccak256.new_stdin();
+    sp1in.write(&n);
+
+    (0..n).for_each(|i| {
+        let input_msg = format!("hello omniverse {}", i * step);
+        sp1in.write_vec(input_msg.as_bytes().to_vec());
+    });
+
+    sp1in
+}
+
+/// The number of keccak256 hashes committed in the public values.
+pub fn hash_count(public_values: &[u8]) -> usize {
+    let (_, pis) = CookSelector::split_public_values(public_values);
+    assert!(pis.len() % 32 == 0, "invalid hash out");
+    pis.len() / 32
+}
+
+/// The committed keccak256 hashes, in the order of the inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keccak256PublicValues {
+    pub hashes: Vec<String>,
+}
+
+impl CookPublicValues for Keccak256PublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (_, pis) = CookSelector::split_public_values(public_values);
+        Keccak256PublicValues {
+            hashes: pis.chunks(32).map(|chunk| format!("0x{}", hex::encode(chunk))).collect(),
+        }
+    }
+}
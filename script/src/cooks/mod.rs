@@ -0,0 +1,9 @@
+pub mod fibonacci;
+pub mod keccak256;
+pub mod poseidon;
+pub mod ecdsa;
+pub mod ecrecover;
+pub mod eip712;
+pub mod hybrid;
+pub mod recursive;
+pub mod p3_verify;
@@ -0,0 +1,159 @@
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolType};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+use tiny_keccak::{Hasher, Keccak};
+
+use base_sp1_p3::utils::p3_air::P3ProveMetrics;
+use sp1_p3_type::types::{chip_id::P3ChipId, fri_config::{FriParams, P3Proof}};
+
+use crate::utils::fixtures::CookPublicValues;
+
+/// The chip id, FRI parameters and keccak256 of the bincode uni-STARK proof of one p3 proof, whose
+/// keccak256 is the digest of the proof.
+pub type ProofValuesTuple = sol! {
+    tuple(uint8, uint32, uint32, uint32, bytes32)
+};
+
+/// The public values committed by the `p3agg` program, `abi.encode(uint8[] chips, bytes32[] digests)`.
+pub type PublicValuesTuple = sol! {
+    tuple(uint8[], bytes32[])
+};
+
+/// The FRI parameters the p3 proofs are made with, before the overrides of the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum P3Fri {
+    /// `FriParams::DEFAULT`: blowup 2, 100 queries
+    Default,
+    /// `FriParams::COMPRESSED`: blowup 8, 33 queries
+    Compressed,
+}
+
+impl P3Fri {
+    /// The parameters of this preset, with any of them overridden.
+    pub fn params(&self, log_blowup: Option<usize>, num_queries: Option<usize>, proof_of_work_bits: Option<usize>) -> FriParams {
+        let preset = match self {
+            P3Fri::Default => FriParams::DEFAULT,
+            P3Fri::Compressed => FriParams::COMPRESSED,
+        };
+
+        FriParams {
+            log_blowup: log_blowup.unwrap_or(preset.log_blowup),
+            num_queries: num_queries.unwrap_or(preset.num_queries),
+            proof_of_work_bits: proof_of_work_bits.unwrap_or(preset.proof_of_work_bits),
+        }
+    }
+}
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(bytes);
+    let mut digest = [0u8; 32];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+/// One uni-STARK proof of a batch of Poseidon2 permutations.
+pub struct P3Batch {
+    pub chip: P3ChipId,
+    pub proof: P3Proof,
+    pub metrics: P3ProveMetrics,
+}
+
+impl P3Batch {
+    /// `keccak256(abi.encode(chip, logBlowup, numQueries, powBits, proofHash))`, as committed by
+    /// the `p3agg` program, where `proofHash` is the keccak256 of the bincode uni-STARK proof.
+    pub fn digest(&self) -> [u8; 32] {
+        let proof_hash = keccak256(&bincode::serialize(&self.proof.proof).expect("serialize p3 proof error"));
+        let fri = self.proof.fri;
+        keccak256(&ProofValuesTuple::abi_encode_params(&(
+            self.chip.tag(),
+            fri.log_blowup as u32,
+            fri.num_queries as u32,
+            fri.proof_of_work_bits as u32,
+            B256::from(proof_hash),
+        )))
+    }
+}
+
+/// Proves `n` random Poseidon2 permutations with the `chip` of `base_sp1_p3` and the FRI
+/// parameters `fri`, verifying the proof on the host too if `self_verify` is set.
+pub fn prove_batch(n: usize, chip: P3ChipId, fri: FriParams, self_verify: bool) -> P3Batch {
+    let (test_inputs, expected_outputs) = base_sp1_p3::utils::sp1_p3_poseidon2::random_permutations(n);
+
+    let result = base_sp1_p3::utils::sp1_p3_poseidon2::prove_babybear_with(
+        chip,
+        fri,
+        test_inputs,
+        expected_outputs,
+        self_verify,
+    )
+    .expect("prove p3 batch error");
+
+    P3Batch {
+        chip,
+        proof: result.proof,
+        metrics: result.metrics,
+    }
+}
+
+/// Proves a batch of `n` permutations with every chip in `chips`, in parallel.
+pub fn prove_batches(n: usize, chips: &[P3ChipId], fri: FriParams, self_verify: bool) -> Vec<P3Batch> {
+    std::thread::scope(|s| {
+        chips
+            .iter()
+            .map(|&chip| s.spawn(move || prove_batch(n, chip, fri, self_verify)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().expect("p3 proving thread panicked"))
+            .collect()
+    })
+}
+
+/// Writes the count of batches, then the chip id and the uni-STARK proof with its FRI parameters
+/// of each, for the `p3agg` program.
+pub fn build_stdin(batches: &[P3Batch]) -> SP1Stdin {
+    let mut sp1in = SP1Stdin::new();
+    sp1in.write::<usize>(&batches.len());
+
+    batches.iter().for_each(|batch| {
+        sp1in.write::<u8>(&batch.chip.tag());
+        sp1in.write(&batch.proof);
+    });
+
+    sp1in
+}
+
+/// The chips and digests of the verified p3 proofs, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct P3PublicValues {
+    pub chips: Vec<String>,
+    pub digests: Vec<String>,
+}
+
+impl P3PublicValues {
+    /// What the `p3agg` program commits for `batches`.
+    pub fn expected(batches: &[P3Batch]) -> Self {
+        P3PublicValues {
+            chips: batches.iter().map(|batch| batch.chip.to_string()).collect(),
+            digests: batches.iter().map(|batch| format!("0x{}", hex::encode(batch.digest()))).collect(),
+        }
+    }
+}
+
+impl CookPublicValues for P3PublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (tags, digests) = PublicValuesTuple::abi_decode_params(public_values, false).expect("decode p3 public values error");
+        P3PublicValues {
+            chips: tags
+                .into_iter()
+                .map(|tag| P3ChipId::from_tag(tag).expect("unknown p3 chip").to_string())
+                .collect(),
+            digests: digests
+                .into_iter()
+                .map(|digest: B256| format!("0x{}", hex::encode(digest)))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use plonky2_field::goldilocks_field::GoldilocksField;
+use plonky2_field::types::Field;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+
+use crate::utils::cook_selector::CookSelector;
+use crate::utils::fixtures::CookPublicValues;
+
+pub fn build_stdin(n: usize) -> SP1Stdin {
+    type F = GoldilocksField;
+
+    let mut sp1in = CookSelector::Poseidon.new_stdin();
+    sp1in.write(&n);
+
+    (0..n).for_each(|i| {
+        let input_msg = vec![F::from_canonical_usize(i); 10];
+        sp1in.write(&input_msg);
+    });
+
+    sp1in
+}
+
+/// The number of poseidon hashes committed in the public values.
+pub fn hash_count(public_values: &[u8]) -> usize {
+    let (_, pis) = CookSelector::split_public_values(public_values);
+    assert!(pis.len() % 32 == 0, "invalid hash out");
+    pis.len() / 32
+}
+
+/// The committed poseidon hashes, in the order of the inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoseidonPublicValues {
+    pub hashes: Vec<[u64; 4]>,
+}
+
+impl CookPublicValues for PoseidonPublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        let (_, pis) = CookSelector::split_public_values(public_values);
+        PoseidonPublicValues {
+            hashes: pis.chunks(32).map(|chunk| std::array::from_fn(|i| u64::from_le_bytes(chunk[i * 8..i * 8 + 8].try_into().unwrap()))).collect(),
+        }
+    }
+}
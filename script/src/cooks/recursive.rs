@@ -0,0 +1,324 @@
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, ProverClient, SP1CompressedProof, SP1Stdin, SP1VerifyingKey};
+use tracing::info;
+
+use crate::cooks::keccak256;
+use crate::utils::cook_selector::COOK_ELF;
+use crate::utils::fixtures::{CookPublicValues, PROOF_PATH};
+use crate::utils::merkle::{aggregation_leaf, allowlist_digest, MerkleProof, MerkleTree, MERKLE_HASH_LEN};
+
+/// An input to the aggregation program.
+///
+/// Consists of a proof and a verification key.
+pub struct AggregationInput {
+    pub proof: SP1CompressedProof,
+    pub vk: SP1VerifyingKey,
+}
+
+/// Generates `count` compressed keccak256 proofs of `n` hashes each.
+pub fn prove_keccak_inputs(client: &ProverClient, count: usize, n: usize) -> Vec<AggregationInput> {
+    let (keccak_pk, keccak_vk) = client.setup(COOK_ELF);
+
+    (0..count)
+        .map(|k| {
+            let proof = tracing::info_span!("generate keccak proof n={n}").in_scope(|| {
+                // only compressed proof could be made into `syscall_verify_sp1_proof`
+                client
+                    .prove_compressed(&keccak_pk, keccak256::build_stdin_with_step(n, k))
+                    .expect("proving failed")
+            });
+
+            AggregationInput {
+                proof,
+                vk: keccak_vk.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Generates a compressed proof of every `(elf, stdin)` program, which can be of different ELFs.
+pub fn prove_program_inputs(client: &ProverClient, programs: Vec<(&[u8], SP1Stdin)>) -> Vec<AggregationInput> {
+    programs
+        .into_iter()
+        .enumerate()
+        .map(|(i, (elf, stdin))| {
+            let (pk, vk) = client.setup(elf);
+            let proof = tracing::info_span!("generate program proof", i).in_scope(|| {
+                client.prove_compressed(&pk, stdin).expect("proving failed")
+            });
+
+            AggregationInput { proof, vk }
+        })
+        .collect()
+}
+
+/// The distinct vkeys of `inputs`, in order of first appearance.
+pub fn input_vkeys(inputs: &[AggregationInput]) -> Vec<[u32; 8]> {
+    let mut vkeys: Vec<[u32; 8]> = Vec::new();
+    for input in inputs.iter() {
+        let vkey = input.vk.hash_u32();
+        if !vkeys.contains(&vkey) {
+            vkeys.push(vkey);
+        }
+    }
+    vkeys
+}
+
+/// Writes the inputs of the aggregation program.
+///
+/// With an `allowlist`, the aggregation program rejects the proofs of the programs outside it.
+pub fn build_stdin(inputs: Vec<AggregationInput>, allowlist: Option<&[[u32; 8]]>) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+
+    // Write the verification keys.
+    let vkeys = inputs
+        .iter()
+        .map(|input| input.vk.hash_u32())
+        .collect::<Vec<_>>();
+    stdin.write::<Vec<[u32; 8]>>(&vkeys);
+
+    // Write the public values.
+    let public_values = inputs
+        .iter()
+        .map(|input| input.proof.public_values.to_vec())
+        .collect::<Vec<_>>();
+    stdin.write::<Vec<Vec<u8>>>(&public_values);
+
+    // Write the allowlist.
+    stdin.write::<Option<Vec<[u32; 8]>>>(&allowlist.map(|allowlist| allowlist.to_vec()));
+
+    // Write the proofs.
+    //
+    // Note: this data will not actually be read by the aggregation program, instead it will be
+    // witnessed by the prover during the recursive aggregation process inside SP1 itself.
+    for input in inputs {
+        stdin.write_proof(input.proof.proof, input.vk.vk);
+    }
+
+    stdin
+}
+
+/// The leaves of the verified proofs, in the order of `inputs`.
+pub fn aggregation_leaves(inputs: &[AggregationInput]) -> Vec<[u8; MERKLE_HASH_LEN]> {
+    inputs
+        .iter()
+        .map(|input| aggregation_leaf(&input.vk.hash_u32(), input.proof.public_values.as_slice()))
+        .collect()
+}
+
+/// The merkle trees of every aggregation step, level by level, from the groups of the original
+/// proofs up to the single tree of the final aggregation.
+pub struct AggregationLevels {
+    pub arity: usize,
+    pub levels: Vec<Vec<MerkleTree>>,
+    /// The vkeys permitted at every step, ending with the aggregation program's own vkey
+    pub allowlist: Option<Vec<[u32; 8]>>,
+}
+
+impl AggregationLevels {
+    /// Groups the `leaves` of the original proofs by `arity`, level by level, the way
+    /// `aggregate_levels` aggregates them, until no more than `arity` leaves are left for the final
+    /// tree.
+    ///
+    /// Every group of level `k` is proven by the aggregation program, which commits
+    /// `root || allowlist digest`, so its leaf at level `k + 1` is
+    /// `aggregation_leaf(aggregation_vkey, root || allowlist digest)`.
+    pub fn from_leaves(
+        mut leaves: Vec<[u8; MERKLE_HASH_LEN]>,
+        arity: usize,
+        aggregation_vkey: &[u32; 8],
+        allowlist: Option<Vec<[u32; 8]>>,
+    ) -> Self {
+        assert!(arity >= 2, "aggregation arity should be at least 2");
+        assert!(!leaves.is_empty(), "nothing to aggregate");
+
+        let mut levels = AggregationLevels { arity, levels: Vec::new(), allowlist };
+        let digest = levels.allowlist_digest();
+        while leaves.len() > arity {
+            let trees = leaves
+                .chunks(arity)
+                .map(|group| MerkleTree::new(group.to_vec()))
+                .collect::<Vec<_>>();
+            leaves = trees
+                .iter()
+                .map(|tree| aggregation_leaf(aggregation_vkey, &[tree.root(), digest].concat()))
+                .collect();
+            levels.levels.push(trees);
+        }
+        levels.levels.push(vec![MerkleTree::new(leaves)]);
+
+        levels
+    }
+
+    /// The inclusion proofs from the `index`'th original proof up to the final root, one per level.
+    ///
+    /// The leaf of the proof at level `k > 0` is the leaf of the aggregation of the group the
+    /// proof at level `k - 1` is in, `aggregation_leaf(aggregation vkey, root_{k-1} || allowlist
+    /// digest)`, so the path can be checked level by level with the committed allowlist digest.
+    pub fn inclusion_path(&self, mut index: usize) -> Vec<MerkleProof> {
+        self.levels
+            .iter()
+            .map(|trees| {
+                let proof = trees[index / self.arity].proof(index % self.arity);
+                index /= self.arity;
+                proof
+            })
+            .collect()
+    }
+
+    pub fn root(&self) -> [u8; MERKLE_HASH_LEN] {
+        self.levels.last().expect("no aggregation level")[0].root()
+    }
+
+    /// The allowlist digest committed by every step, zero if there is no allowlist.
+    pub fn allowlist_digest(&self) -> [u8; MERKLE_HASH_LEN] {
+        self.allowlist
+            .as_ref()
+            .map(|allowlist| allowlist_digest(allowlist))
+            .unwrap_or([0u8; MERKLE_HASH_LEN])
+    }
+
+    /// `root || allowlist digest`, as committed by the final aggregation.
+    pub fn expected_public_values(&self) -> Vec<u8> {
+        [self.root(), self.allowlist_digest()].concat()
+    }
+}
+
+/// `root || allowlist digest`, as committed by the aggregation program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationPublicValues {
+    pub root: String,
+    pub allowlist_digest: String,
+}
+
+impl CookPublicValues for AggregationPublicValues {
+    fn decode(public_values: &[u8]) -> Self {
+        assert_eq!(public_values.len(), 2 * MERKLE_HASH_LEN, "invalid aggregation public values");
+        let (root, allowlist_digest) = public_values.split_at(MERKLE_HASH_LEN);
+        AggregationPublicValues {
+            root: format!("0x{}", hex::encode(root)),
+            allowlist_digest: format!("0x{}", hex::encode(allowlist_digest)),
+        }
+    }
+}
+
+/// Groups `inputs` by `arity` and aggregates every group into one compressed proof, level by
+/// level, until no more than `arity` proofs are left for the final aggregation.
+///
+/// With `permitted` vkeys, every step only accepts those and the aggregation program's own vkey,
+/// whose proofs must have committed the same allowlist digest.
+///
+/// Returns the inputs of the final aggregation, and the levels with the final tree pushed last.
+pub fn aggregate_levels(
+    client: &ProverClient,
+    aggregation_elf: &[u8],
+    mut inputs: Vec<AggregationInput>,
+    arity: usize,
+    permitted: Option<Vec<[u32; 8]>>,
+) -> (Vec<AggregationInput>, AggregationLevels) {
+    let (aggregation_pk, aggregation_vk) = client.setup(aggregation_elf);
+    let aggregation_vkey = aggregation_vk.hash_u32();
+    // the aggregation program's own vkey goes last, so nested aggregates are checked against it
+    let allowlist = permitted.map(|mut allowlist| {
+        allowlist.retain(|vkey| *vkey != aggregation_vkey);
+        allowlist.push(aggregation_vkey);
+        allowlist
+    });
+    let levels = AggregationLevels::from_leaves(aggregation_leaves(&inputs), arity, &aggregation_vkey, allowlist);
+    let digest = levels.allowlist_digest();
+
+    for (level, trees) in levels.levels[..levels.levels.len() - 1].iter().enumerate() {
+        info!("aggregating {} proofs at level {}", inputs.len(), level);
+
+        let mut remaining = inputs.into_iter();
+        inputs = trees
+            .iter()
+            .enumerate()
+            .map(|(group, tree)| {
+                let group_inputs = remaining.by_ref().take(arity).collect::<Vec<_>>();
+                let proof = tracing::info_span!("aggregate group", level, group).in_scope(|| {
+                    client
+                        .prove_compressed(&aggregation_pk, build_stdin(group_inputs, levels.allowlist.as_deref()))
+                        .expect("proving failed")
+                });
+                assert_eq!(
+                    proof.public_values.as_slice(),
+                    [tree.root(), digest].concat(),
+                    "aggregated group root mismatch"
+                );
+
+                AggregationInput {
+                    proof,
+                    vk: aggregation_vk.clone(),
+                }
+            })
+            .collect();
+    }
+
+    (inputs, levels)
+}
+
+/// Checks the committed root and allowlist digest against `levels`, then saves the inclusion path
+/// of every original proof to `{PROOF_PATH}{name}-inclusion-proofs.json`.
+pub fn save_inclusion_proofs(name: &str, levels: &AggregationLevels, public_values: &[u8]) {
+    assert_eq!(public_values, levels.expected_public_values(), "committed merkle root mismatch");
+
+    let leaf_count = levels.levels[0].iter().map(|tree| tree.leaf_count()).sum::<usize>();
+    let paths = (0..leaf_count)
+        .map(|i| levels.inclusion_path(i))
+        .collect::<Vec<Vec<MerkleProof>>>();
+
+    std::fs::create_dir_all(PROOF_PATH).expect("failed to create proof path");
+    std::fs::write(
+        format!("{}{}-inclusion-proofs.json", PROOF_PATH, name),
+        serde_json::to_string_pretty(&paths).unwrap(),
+    )
+    .expect("failed to write inclusion proofs");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_levels(leaf_count: usize, arity: usize, allowlist: Option<Vec<[u32; 8]>>) {
+        let aggregation_vkey = [7u32; 8];
+        let leaves = (0..leaf_count).map(|i| [i as u8 + 1; MERKLE_HASH_LEN]).collect::<Vec<_>>();
+        let levels = AggregationLevels::from_leaves(leaves.clone(), arity, &aggregation_vkey, allowlist);
+        assert_eq!(levels.levels.last().unwrap().len(), 1);
+        assert!(levels.levels.last().unwrap()[0].leaf_count() <= arity);
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = levels.inclusion_path(i);
+            assert_eq!(path.len(), levels.levels.len());
+            assert_eq!(path[0].leaf, *leaf);
+
+            // every level's leaf is the aggregation of the root of the level below
+            for k in 1..path.len() {
+                let committed = [path[k - 1].compute_root(), levels.allowlist_digest()].concat();
+                assert_eq!(path[k].leaf, aggregation_leaf(&aggregation_vkey, &committed));
+            }
+            assert_eq!(path.last().unwrap().compute_root(), levels.root());
+        }
+    }
+
+    #[test]
+    fn test_inclusion_paths() {
+        check_levels(1, 2, None);
+        check_levels(2, 2, None);
+        check_levels(9, 2, None);
+        check_levels(10, 3, Some(vec![[1u32; 8], [7u32; 8]]));
+        check_levels(27, 3, None);
+    }
+
+    #[test]
+    fn test_levels_grouping() {
+        let levels = AggregationLevels::from_leaves(vec![[1u8; MERKLE_HASH_LEN]; 10], 3, &[7u32; 8], None);
+        let groups = levels
+            .levels
+            .iter()
+            .map(|trees| trees.iter().map(|tree| tree.leaf_count()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(groups, vec![vec![3, 3, 3, 1], vec![3, 1], vec![2]]);
+    }
+}
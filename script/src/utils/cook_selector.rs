@@ -0,0 +1,68 @@
+use sp1_sdk::SP1Stdin;
+
+/// The ELF shared by all the cooks in `program/src/cooks`.
+///
+/// This file is generated by running `cargo prove build` inside the `program` directory.
+pub const COOK_ELF: &[u8] = include_bytes!("../../../program/elf/riscv32im-succinct-zkvm-elf");
+
+/// Host-side mirror of `fibonacci_program::cooks::CookSelector`, keep the tags in sync.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookSelector {
+    Fibonacci = 0,
+    Keccak256 = 1,
+    Poseidon = 2,
+    VerifyEcdsa = 3,
+    EcdsaRecover = 4,
+    VerifyPlonky2Proof = 5,
+}
+
+impl CookSelector {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(CookSelector::Fibonacci),
+            1 => Some(CookSelector::Keccak256),
+            2 => Some(CookSelector::Poseidon),
+            3 => Some(CookSelector::VerifyEcdsa),
+            4 => Some(CookSelector::EcdsaRecover),
+            5 => Some(CookSelector::VerifyPlonky2Proof),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Creates the inputs of the cook program, with the selector tag written first.
+    pub fn new_stdin(&self) -> SP1Stdin {
+        let mut sp1in = SP1Stdin::new();
+        sp1in.write::<u8>(&self.tag());
+        sp1in
+    }
+
+    /// Splits the committed public values into the selector and the cook's own public values.
+    pub fn split_public_values(public_values: &[u8]) -> (Option<Self>, &[u8]) {
+        match public_values.split_first() {
+            Some((tag, rest)) => (Self::from_tag(*tag), rest),
+            None => (None, public_values),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_public_values() {
+        let pis = [CookSelector::Keccak256.tag(), 1, 2, 3];
+        let (selector, rest) = CookSelector::split_public_values(&pis);
+        assert_eq!(selector, Some(CookSelector::Keccak256));
+        assert_eq!(rest, &[1, 2, 3]);
+
+        let (selector, rest) = CookSelector::split_public_values(&[]);
+        assert_eq!(selector, None);
+        assert!(rest.is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+/// The errors of loading and saving the fixtures and proofs of the cooks.
+#[derive(Debug, Error)]
+pub enum CookError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    /// Bytes that are no valid plonky2 circuit data.
+    #[error("decode error: {0}")]
+    Decode(String),
+
+    #[error("verification failed: {0}")]
+    Verification(String),
+
+    #[error("vk mismatch: expected {expected}, found {found}")]
+    VkMismatch { expected: String, found: String },
+
+    #[error("elf hash mismatch: expected {expected}, found {found}")]
+    ElfMismatch { expected: String, found: String },
+
+    /// An error of the SP1 SDK saving or loading a proof.
+    #[error("proof error: {0}")]
+    Proof(String),
+
+    #[error("system time error: {0}")]
+    Time(#[from] std::time::SystemTimeError),
+
+    #[error("no artifact with id {0}")]
+    NotFound(String),
+
+    #[error("artifact {0} already exists")]
+    AlreadyExists(String),
+
+    #[error("an execution has no proof to store")]
+    NoProof,
+}
+
+pub type CookResult<T> = Result<T, CookError>;
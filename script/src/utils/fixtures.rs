@@ -1,60 +1,130 @@
+use std::path::Path;
 
-use serde::{Deserialize, Serialize};
-use sp1_sdk::{HashableKey, SP1PlonkBn254Proof, SP1VerifyingKey};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_sdk::{HashableKey, SP1CompressedProof, SP1PlonkBn254Proof, SP1Proof, SP1PublicValues, SP1VerifyingKey};
+
+use super::error::{CookError, CookResult};
+use super::proof_store::ProofKind;
 
 pub const FIXTURE_PATH: &str = "../contracts/src/fixtures/";
 pub const PROOF_PATH: &str = "./proof-bin/";
 
 //////////////////////////////////////////////////////////////////////
 // trait
-pub trait FixtureBuilder<'a>: Clone + Serialize + Deserialize<'a> {
-    fn from_sp1_plonk_bn254_proof_vk(proof: &SP1PlonkBn254Proof, vk: &SP1VerifyingKey) -> Self;
-    fn from_sp1_plonk_bn254_proof_vk_hash(proof: &SP1PlonkBn254Proof, vk_hash: String) -> Self;
+pub trait FixtureBuilder: Clone + Serialize + DeserializeOwned {
+    fn from_proof_vk<P: FixtureProof>(proof: &P, vk: &SP1VerifyingKey) -> Self;
+    fn from_proof_vk_hash<P: FixtureProof>(proof: &P, vk_hash: String) -> Self;
+
+    fn save_to_local(&self, path: &Path) -> CookResult<()>;
+    fn load_from_local(path: &Path) -> CookResult<Self>;
 
-    fn save_to_local(&self, filename: &String);
-    fn load_from_local(filename: &String) -> Self;
+    /// Checks that the fixture is of the program with the verifying key `vk`.
+    fn check_vk(&self, vk: &SP1VerifyingKey) -> CookResult<()>;
+}
+
+/// A proof the SDK returns, which can be put into a fixture.
+pub trait FixtureProof {
+    const KIND: ProofKind;
+
+    fn public_values(&self) -> &SP1PublicValues;
+
+    /// The hex encoded proof, `0x` prefixed.
+    fn proof_bytes(&self) -> String;
+}
+
+impl FixtureProof for SP1PlonkBn254Proof {
+    const KIND: ProofKind = ProofKind::Plonk;
+
+    fn public_values(&self) -> &SP1PublicValues {
+        &self.public_values
+    }
+
+    fn proof_bytes(&self) -> String {
+        self.bytes()
+    }
+}
+
+// The STARK proofs cannot be verified on-chain, so they are kept bincode serialized for the
+// off-chain verifiers.
+impl FixtureProof for SP1CompressedProof {
+    const KIND: ProofKind = ProofKind::Compressed;
+
+    fn public_values(&self) -> &SP1PublicValues {
+        &self.public_values
+    }
+
+    fn proof_bytes(&self) -> String {
+        format!("0x{}", hex::encode(bincode::serialize(&self.proof).unwrap()))
+    }
+}
+
+impl FixtureProof for SP1Proof {
+    const KIND: ProofKind = ProofKind::Core;
+
+    fn public_values(&self) -> &SP1PublicValues {
+        &self.public_values
+    }
+
+    fn proof_bytes(&self) -> String {
+        format!("0x{}", hex::encode(bincode::serialize(&self.proof).unwrap()))
+    }
+}
+
+/// The public values of a cook, decoded into a typed section of its fixture.
+pub trait CookPublicValues: Clone + Serialize + DeserializeOwned {
+    fn decode(public_values: &[u8]) -> Self;
 }
 
 //////////////////////////////////////////////////////////////////////
-// ec-recover
+// fixture shared by all the cooks
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct SP1ProofFixture {
-    vkey_hash: String,
-    public_values: String,
-    proof: String,
+#[serde(bound = "D: CookPublicValues")]
+pub struct SP1ProofFixture<D> {
+    pub vkey_hash: String,
+    pub kind: ProofKind,
+    pub public_values: String,
+    pub proof: String,
+    pub decoded: D,
 }
 
-impl<'a> FixtureBuilder<'a> for SP1ProofFixture {
-    fn from_sp1_plonk_bn254_proof_vk(proof: &SP1PlonkBn254Proof, vk: &SP1VerifyingKey) -> Self {
-        SP1ProofFixture {
-            vkey_hash: vk.bytes32().to_string(),
-            public_values: proof.public_values.bytes().to_string(),
-            proof: proof.bytes().to_string(),
-        }
+impl<D: CookPublicValues> FixtureBuilder for SP1ProofFixture<D> {
+    fn from_proof_vk<P: FixtureProof>(proof: &P, vk: &SP1VerifyingKey) -> Self {
+        Self::from_proof_vk_hash(proof, vk.bytes32())
     }
 
-    fn from_sp1_plonk_bn254_proof_vk_hash(proof: &SP1PlonkBn254Proof, vkey_hash: String) -> Self {
+    fn from_proof_vk_hash<P: FixtureProof>(proof: &P, vkey_hash: String) -> Self {
         SP1ProofFixture {
             vkey_hash,
-            public_values: proof.public_values.bytes().to_string(),
-            proof: proof.bytes().to_string(),
+            kind: P::KIND,
+            public_values: proof.public_values().bytes().to_string(),
+            proof: proof.proof_bytes(),
+            decoded: D::decode(proof.public_values().as_slice()),
+        }
+    }
+
+    fn save_to_local(&self, path: &Path) -> CookResult<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
         }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
     }
 
-    fn save_to_local(&self, filename: &String) {
-        std::fs::create_dir_all(FIXTURE_PATH).expect("failed to create fixture path");
-        std::fs::write(
-            format!("{}{}", FIXTURE_PATH, filename),
-            serde_json::to_string_pretty(self).unwrap(),
-        )
-        .expect("failed to write fixture");
+    fn load_from_local(path: &Path) -> CookResult<Self> {
+        let fixture_buf = std::fs::read(path)?;
+        let sp1_fixture: SP1ProofFixture<D> = serde_json::from_slice(&fixture_buf)?;
+        Ok(sp1_fixture)
     }
 
-    fn load_from_local(filename: &String) -> Self {
-        let fixture_buf = std::fs::read(format!("{}{}", FIXTURE_PATH, filename)).expect("load common fixture file error");
-        let sp1_fixture: SP1ProofFixture = serde_json::from_slice(&fixture_buf).expect("deserilize fixture file error");
-        sp1_fixture
+    fn check_vk(&self, vk: &SP1VerifyingKey) -> CookResult<()> {
+        let found = vk.bytes32();
+        if self.vkey_hash != found {
+            return Err(CookError::VkMismatch {
+                expected: self.vkey_hash.clone(),
+                found,
+            });
+        }
+        Ok(())
     }
 }
 
@@ -63,30 +133,37 @@ mod tests {
 
     use sp1_sdk::ProverClient;
     use tracing::info;
+    use crate::cooks::ecrecover::EcrecoverPublicValues;
+    use crate::utils::{cook_selector::COOK_ELF, proof_store::{ProofKind, ProofStore}, runner::{fixture_file, CookOutput}};
     use super::*;
 
     #[test]
     fn test_ecrecover_fixture() {
         sp1_sdk::utils::setup_logger();
 
-        let ecr_bn254_proof = SP1PlonkBn254Proof::load("./proof-bin/ecrecover-ppis.bin").expect("load ecr-ppis error");
+        let store = ProofStore::open(PROOF_PATH).expect("open proof store error");
+        let entry = store.latest("ecrecover", ProofKind::Plonk).expect("no ecrecover plonk proof stored");
         // call circuit
         // Setup the prover client.
         let client = ProverClient::new();
 
-        // Setup the program.
-        const ECRECOVER_ELF: &[u8] = include_bytes!("../../../program/elf/riscv32im-succinct-zkvm-elf");
-        let (_pk, vk) = client.setup(ECRECOVER_ELF);
-        info!("{}", vk.bytes32());
-        client
-            .verify_plonk(&ecr_bn254_proof, &vk)
-            .expect("verification failed");
+        // Verify the stored proof against the program.
+        store.verify(&client, &entry.id, COOK_ELF).expect("verification failed");
+        let ecr_bn254_proof = match store.load(&entry.id).expect("load ecr-ppis error") {
+            CookOutput::Plonk(proof) => proof,
+            _ => unreachable!(),
+        };
 
-        let ecr_vk_hash = std::fs::read_to_string(format!("{}{}", PROOF_PATH, "ecrecover-vk-hash")).expect("load vk hash failed");
+        let ecr_vk_hash = entry.vk_hash.clone();
         info!("{}", ecr_vk_hash);
 
-        let ecrecover_proof_fixture = SP1ProofFixture:: from_sp1_plonk_bn254_proof_vk_hash(&ecr_bn254_proof, ecr_vk_hash);
+        let ecrecover_proof_fixture = SP1ProofFixture::<EcrecoverPublicValues>::from_proof_vk_hash(&ecr_bn254_proof, ecr_vk_hash);
+
+        let fixture_path = Path::new(FIXTURE_PATH).join(fixture_file("ecrecover", ProofKind::Plonk));
+        ecrecover_proof_fixture.save_to_local(&fixture_path).expect("save ecrecover fixture error");
 
-        ecrecover_proof_fixture.save_to_local(&"ecr-fixture.json".to_string());
+        let (_, vk) = client.setup(COOK_ELF);
+        let loaded = SP1ProofFixture::<EcrecoverPublicValues>::load_from_local(&fixture_path).expect("load ecrecover fixture error");
+        loaded.check_vk(&vk).expect("ecrecover fixture vk mismatch");
     }
 }
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tiny_keccak::{Hasher, Keccak};
+
+pub const MERKLE_HASH_LEN: usize = 32;
+
+pub fn words_to_bytes_le(words: &[u32; 8]) -> [u8; MERKLE_HASH_LEN] {
+    let mut bytes = [0u8; MERKLE_HASH_LEN];
+    for i in 0..8 {
+        bytes[i * 4..(i + 1) * 4].copy_from_slice(&words[i].to_le_bytes());
+    }
+    bytes
+}
+
+fn hash_pair(left: &[u8; MERKLE_HASH_LEN], right: &[u8; MERKLE_HASH_LEN]) -> [u8; MERKLE_HASH_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn keccak_hash_pair(left: &[u8; MERKLE_HASH_LEN], right: &[u8; MERKLE_HASH_LEN]) -> [u8; MERKLE_HASH_LEN] {
+    let mut hasher = Keccak::v256();
+    hasher.update(left);
+    hasher.update(right);
+    let mut output = [0u8; MERKLE_HASH_LEN];
+    hasher.finalize(&mut output);
+    output
+}
+
+/// The hash of the inner nodes of a `MerkleTree`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MerkleHash {
+    /// the tree of the `recursive` program
+    #[default]
+    Sha256,
+    /// the txid tree of the `sp1eip712` program, `keccak256(abi.encodePacked(left, right))`
+    Keccak256,
+}
+
+impl MerkleHash {
+    pub fn hash_pair(&self, left: &[u8; MERKLE_HASH_LEN], right: &[u8; MERKLE_HASH_LEN]) -> [u8; MERKLE_HASH_LEN] {
+        match self {
+            MerkleHash::Sha256 => hash_pair(left, right),
+            MerkleHash::Keccak256 => keccak_hash_pair(left, right),
+        }
+    }
+}
+
+/// The leaf committed by the `recursive` program for a verified proof:
+/// `sha256(vkey || sha256(public_values))`.
+pub fn aggregation_leaf(vkey: &[u32; 8], public_values: &[u8]) -> [u8; MERKLE_HASH_LEN] {
+    let public_values_digest: [u8; MERKLE_HASH_LEN] = Sha256::digest(public_values).into();
+    hash_pair(&words_to_bytes_le(vkey), &public_values_digest)
+}
+
+/// The digest of the vkeys permitted by the `recursive` program:
+/// `sha256(allowlist[0] || allowlist[1] || ...)`.
+pub fn allowlist_digest(allowlist: &[[u32; 8]]) -> [u8; MERKLE_HASH_LEN] {
+    let mut hasher = Sha256::new();
+    for vkey in allowlist.iter() {
+        hasher.update(words_to_bytes_le(vkey));
+    }
+    hasher.finalize().into()
+}
+
+/// The merkle tree built by the `recursive` program with sha256, or by the `sp1eip712` program
+/// over the txids with keccak256, padded with zero leaves up to the next power of two.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    /// `layers[0]` are the padded leaves, the last layer is the root
+    layers: Vec<Vec<[u8; MERKLE_HASH_LEN]>>,
+    leaf_count: usize,
+    hash: MerkleHash,
+}
+
+/// Serializes a hash as `0x`-prefixed hex, which Foundry reads as a `bytes32`.
+mod hex_hash {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::MERKLE_HASH_LEN;
+
+    pub fn to_hex(hash: &[u8; MERKLE_HASH_LEN]) -> String {
+        format!("0x{}", hex::encode(hash))
+    }
+
+    pub fn from_hex<E: Error>(s: &str) -> Result<[u8; MERKLE_HASH_LEN], E> {
+        let bytes = hex::decode(s.trim_start_matches("0x")).map_err(E::custom)?;
+        bytes.try_into().map_err(|_| E::custom(format!("not a 32-byte hash: {}", s)))
+    }
+
+    pub fn serialize<S: Serializer>(hash: &[u8; MERKLE_HASH_LEN], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; MERKLE_HASH_LEN], D::Error> {
+        from_hex(&String::deserialize(deserializer)?)
+    }
+}
+
+/// `hex_hash` for a list of hashes, which Foundry reads as a `bytes32[]`.
+mod hex_hashes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::{hex_hash, MERKLE_HASH_LEN};
+
+    pub fn serialize<S: Serializer>(hashes: &[[u8; MERKLE_HASH_LEN]], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(hashes.iter().map(hex_hash::to_hex))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<[u8; MERKLE_HASH_LEN]>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| hex_hash::from_hex(s))
+            .collect()
+    }
+}
+
+/// The siblings from a leaf up to the root, with every hash serialized as `0x`-prefixed hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleProof {
+    pub index: usize,
+    #[serde(with = "hex_hash")]
+    pub leaf: [u8; MERKLE_HASH_LEN],
+    #[serde(with = "hex_hashes")]
+    pub siblings: Vec<[u8; MERKLE_HASH_LEN]>,
+    #[serde(default)]
+    pub hash: MerkleHash,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; MERKLE_HASH_LEN]>) -> Self {
+        Self::with_hash(leaves, MerkleHash::Sha256)
+    }
+
+    pub fn with_hash(leaves: Vec<[u8; MERKLE_HASH_LEN]>, hash: MerkleHash) -> Self {
+        let leaf_count = leaves.len();
+        let mut layer = leaves;
+        if !layer.is_empty() {
+            layer.resize(leaf_count.next_power_of_two(), [0u8; MERKLE_HASH_LEN]);
+        }
+
+        let mut layers = vec![layer];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash.hash_pair(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+
+        MerkleTree { layers, leaf_count, hash }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// The root of no leaves is zero.
+    pub fn root(&self) -> [u8; MERKLE_HASH_LEN] {
+        self.layers.last().unwrap().first().copied().unwrap_or([0u8; MERKLE_HASH_LEN])
+    }
+
+    pub fn proof(&self, index: usize) -> MerkleProof {
+        assert!(index < self.leaf_count, "leaf index out of range");
+
+        let siblings = self.layers[..self.layers.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1])
+            .collect();
+
+        MerkleProof {
+            index,
+            leaf: self.layers[0][index],
+            siblings,
+            hash: self.hash,
+        }
+    }
+}
+
+impl MerkleProof {
+    pub fn compute_root(&self) -> [u8; MERKLE_HASH_LEN] {
+        self.siblings
+            .iter()
+            .enumerate()
+            .fold(self.leaf, |node, (level, sibling)| {
+                if (self.index >> level) & 1 == 0 {
+                    self.hash.hash_pair(&node, sibling)
+                } else {
+                    self.hash.hash_pair(sibling, &node)
+                }
+            })
+    }
+
+    pub fn verify(&self, root: &[u8; MERKLE_HASH_LEN]) -> bool {
+        self.compute_root() == *root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_proofs() {
+        let leaves = (0..5u32)
+            .map(|i| aggregation_leaf(&[i; 8], format!("public values {i}").as_bytes()))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(leaves.clone());
+
+        // padded up to 8 leaves, so 3 levels
+        let zero = [0u8; MERKLE_HASH_LEN];
+        let l1 = [
+            hash_pair(&leaves[0], &leaves[1]),
+            hash_pair(&leaves[2], &leaves[3]),
+            hash_pair(&leaves[4], &zero),
+            hash_pair(&zero, &zero),
+        ];
+        let l2 = [hash_pair(&l1[0], &l1[1]), hash_pair(&l1[2], &l1[3])];
+        assert_eq!(tree.root(), hash_pair(&l2[0], &l2[1]));
+
+        for i in 0..leaves.len() {
+            let proof = tree.proof(i);
+            assert_eq!(proof.leaf, leaves[i]);
+            assert_eq!(proof.siblings.len(), 3);
+            assert!(proof.verify(&tree.root()));
+        }
+
+        let mut bad_proof = tree.proof(2);
+        bad_proof.index = 3;
+        assert!(!bad_proof.verify(&tree.root()));
+    }
+
+    #[test]
+    fn test_single_leaf() {
+        let leaf = aggregation_leaf(&[7; 8], b"only");
+        let tree = MerkleTree::new(vec![leaf]);
+        assert_eq!(tree.root(), leaf);
+        assert!(tree.proof(0).siblings.is_empty());
+        assert_eq!(MerkleTree::new(vec![]).root(), [0u8; MERKLE_HASH_LEN]);
+    }
+
+    #[test]
+    fn test_proof_json() {
+        let tree = MerkleTree::with_hash(vec![[1u8; MERKLE_HASH_LEN], [2u8; MERKLE_HASH_LEN]], MerkleHash::Keccak256);
+        let proof = tree.proof(0);
+
+        let json = serde_json::to_value(&proof).unwrap();
+        assert_eq!(json["leaf"], format!("0x{}", "01".repeat(MERKLE_HASH_LEN)));
+        assert_eq!(json["siblings"][0], format!("0x{}", "02".repeat(MERKLE_HASH_LEN)));
+        assert_eq!(json["hash"], "keccak256");
+        assert_eq!(serde_json::from_value::<MerkleProof>(json).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_keccak_txid_tree() {
+        let txids = (1..=3u8).map(|i| [i; MERKLE_HASH_LEN]).collect::<Vec<_>>();
+        let tree = MerkleTree::with_hash(txids.clone(), MerkleHash::Keccak256);
+
+        // the vector of `contracts/test/TxidMerkle.t.sol`
+        let root = hex::decode("b3eab8646d706247bc8e454e8850ea39af6dbe9bdc9c7751778da0e95d177c34").unwrap();
+        assert_eq!(tree.root().to_vec(), root);
+
+        let proof = tree.proof(2);
+        assert_eq!(proof.hash, MerkleHash::Keccak256);
+        assert_eq!(proof.siblings[0], [0u8; MERKLE_HASH_LEN]);
+        assert!(proof.verify(&tree.root()));
+        assert!(!MerkleProof { hash: MerkleHash::Sha256, ..proof }.verify(&tree.root()));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod unit_tests;
+pub mod error;
 pub mod fixtures;
-pub mod p2_proof;
\ No newline at end of file
+pub mod p2_proof;
+pub mod cook_selector;
+pub mod runner;
+pub mod report;
+pub mod merkle;
+pub mod proof_store;
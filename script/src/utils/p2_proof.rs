@@ -1,42 +1,357 @@
 use std::fs;
-use anyhow::Result;
+use std::path::{Path, PathBuf};
 
-use plonky2::{hash::hash_types::RichField, plonk::{circuit_data::{CommonCircuitData, VerifierCircuitData, VerifierOnlyCircuitData}, config::GenericConfig, proof::ProofWithPublicInputs}, util::serialization::DefaultGateSerializer};
-use plonky2_field::extension::Extendable;
+use clap::ValueEnum;
+use plonky2::{hash::hash_types::RichField, iop::witness::{PartialWitness, WitnessWrite}, plonk::{circuit_builder::CircuitBuilder, circuit_data::{CircuitConfig, CommonCircuitData, VerifierCircuitData, VerifierOnlyCircuitData}, config::{GenericConfig, GenericHashOut}, proof::ProofWithPublicInputs}, util::serialization::DefaultGateSerializer};
+use plonky2_field::{extension::Extendable, types::Field};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-const FRI_PROOF_DIR: &str = "./.p2-data";
+use super::error::{CookError, CookResult};
 
-// `CommonCircuitData` did not implemented the `serde::Deserialize`
-pub fn load_p2_proof
-<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
-(hrr_proof_id: &str) -> Result<(ProofWithPublicInputs<F, C, D>, VerifierOnlyCircuitData<C, D>, Vec<u8>)> 
-{
+pub const FRI_PROOF_DIR: &str = "./.p2-data";
+
+/// The plonky2 config of the proofs verified by the `p2agg` program, written as a tag before them.
+///
+/// Keep in sync with `fibonacci_program::cooks::verify_p2_proof::P2Config`.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum P2Config {
+    /// `PoseidonGoldilocksConfig`
+    Poseidon = 0,
+    /// `KeccakGoldilocksConfig`
+    Keccak = 1,
+}
+
+impl P2Config {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(P2Config::Poseidon),
+            1 => Some(P2Config::Keccak),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// The canonical elements of a hash, which are 4 for both the poseidon and the 25-byte keccak hashes.
+pub fn hash_elements<F: RichField>(hash: impl GenericHashOut<F>) -> [u64; 4] {
+    hash.to_vec()
+        .iter()
+        .map(|e| e.to_canonical_u64())
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("unexpected hash length")
+}
+
+/// How the verifier only and common circuit data of a stored plonky2 proof are encoded.
+///
+/// `CommonCircuitData` has no serde support, so the json and bincode formats wrap its
+/// gate-serialized bytes, as a hex string and a byte vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum P2DataFormat {
+    /// plonky2's own `to_bytes`, as written by zk-6358
+    Plonky2,
+    Json,
+    Bincode,
+}
+
+impl P2DataFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            P2DataFormat::Plonky2 => "",
+            P2DataFormat::Json => ".json",
+            P2DataFormat::Bincode => ".bin",
+        }
+    }
+}
+
+/// The files of a stored plonky2 proof.
+#[derive(Debug, Clone)]
+pub struct P2ProofPaths {
+    pub format: P2DataFormat,
+    /// The `VerifierOnlyCircuitData` bytes
+    pub vod: PathBuf,
+    /// The `CommonCircuitData` bytes
+    pub ccd: PathBuf,
+    /// The json `ProofWithPublicInputs`
+    pub ppis: PathBuf,
+    /// The cached circuit digest of `vod`, with the hash of the `vod` it was decoded from
+    pub digest: PathBuf,
+}
+
+impl P2ProofPaths {
+    /// `{dir}/{proof_id}_vod`, `{dir}/{proof_id}_ccd`, `{dir}/{proof_id}_ppis.json` and
+    /// `{dir}/{proof_id}_digest.json`.
+    pub fn in_dir(dir: impl AsRef<Path>, proof_id: &str) -> Self {
+        Self::in_dir_with_format(dir, proof_id, P2DataFormat::Plonky2)
+    }
+
+    /// `in_dir`, with the `.json` or `.bin` extension on the circuit data files of those formats.
+    pub fn in_dir_with_format(dir: impl AsRef<Path>, proof_id: &str, format: P2DataFormat) -> Self {
+        let dir = dir.as_ref();
+        P2ProofPaths {
+            format,
+            vod: dir.join(format!("{}_vod{}", proof_id, format.extension())),
+            ccd: dir.join(format!("{}_ccd{}", proof_id, format.extension())),
+            ppis: dir.join(format!("{}_ppis.json", proof_id)),
+            digest: dir.join(format!("{}_digest.json", proof_id)),
+        }
+    }
+}
+
+/// The `VerifierOnlyCircuitData` at `paths.vod`, in `paths.format`.
+pub fn read_verifier_only<C: GenericConfig<D>, const D: usize>(paths: &P2ProofPaths) -> CookResult<VerifierOnlyCircuitData<C, D>> {
+    let vod = fs::read(&paths.vod)?;
+    Ok(match paths.format {
+        P2DataFormat::Plonky2 => VerifierOnlyCircuitData::<C, D>::from_bytes(vod)
+            .map_err(|e| CookError::Decode(format!("verifier only circuit data: {:?}", e)))?,
+        P2DataFormat::Json => serde_json::from_slice(&vod)?,
+        P2DataFormat::Bincode => bincode::deserialize(&vod)?,
+    })
+}
+
+/// The gate-serialized `CommonCircuitData` bytes, which the `p2agg` program decodes.
+pub fn read_common_bytes(paths: &P2ProofPaths) -> CookResult<Vec<u8>> {
+    let ccd = fs::read(&paths.ccd)?;
+    Ok(match paths.format {
+        P2DataFormat::Plonky2 => ccd,
+        P2DataFormat::Json => {
+            let ccd_hex: String = serde_json::from_slice(&ccd)?;
+            hex::decode(ccd_hex.trim_start_matches("0x"))
+                .map_err(|e| CookError::Decode(format!("common circuit data: {}", e)))?
+        }
+        P2DataFormat::Bincode => bincode::deserialize(&ccd)?,
+    })
+}
 
-    let vod_path = format!("{}/{}_vod", FRI_PROOF_DIR, hrr_proof_id);
-    let ccd_path = format!("{}/{}_ccd", FRI_PROOF_DIR, hrr_proof_id);
-    let ppis_path = format!("{}/{}_ppis.json", FRI_PROOF_DIR, hrr_proof_id);
+fn decode_common<F: RichField + Extendable<D>, const D: usize>(ccd: &[u8]) -> CookResult<CommonCircuitData<F, D>> {
+    CommonCircuitData::<F, D>::from_bytes(ccd.to_vec(), &DefaultGateSerializer)
+        .map_err(|e| CookError::Decode(format!("common circuit data: {:?}", e)))
+}
+
+/// Writes a plonky2 proof and its circuit data to `paths`, in `paths.format`, replacing the cached
+/// circuit digest.
+pub fn save_p2_proof<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+    paths: &P2ProofPaths,
+    proof: &ProofWithPublicInputs<F, C, D>,
+    verifier_data: &VerifierCircuitData<F, C, D>,
+) -> CookResult<()> {
+    if let Some(dir) = paths.ppis.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let ccd = verifier_data
+        .common
+        .to_bytes(&DefaultGateSerializer)
+        .map_err(|e| CookError::Decode(format!("common circuit data: {:?}", e)))?;
+    let (vod, ccd) = match paths.format {
+        P2DataFormat::Plonky2 => (
+            verifier_data
+                .verifier_only
+                .to_bytes()
+                .map_err(|e| CookError::Decode(format!("verifier only circuit data: {:?}", e)))?,
+            ccd,
+        ),
+        P2DataFormat::Json => (
+            serde_json::to_vec(&verifier_data.verifier_only)?,
+            serde_json::to_vec(&format!("0x{}", hex::encode(ccd)))?,
+        ),
+        P2DataFormat::Bincode => (
+            bincode::serialize(&verifier_data.verifier_only)?,
+            bincode::serialize(&ccd)?,
+        ),
+    };
+    let circuit_digest = hash_elements(verifier_data.verifier_only.circuit_digest);
+    fs::write(&paths.vod, &vod)?;
+    fs::write(&paths.ccd, ccd)?;
+    fs::write(&paths.ppis, serde_json::to_vec(proof)?)?;
+    store_circuit_digest(paths, &vod, circuit_digest)?;
+
+    Ok(())
+}
 
-    let vod = fs::read(vod_path).unwrap();
-    let verifier_only = VerifierOnlyCircuitData::<C, D>::from_bytes(vod).unwrap();
+/// Proves a tiny sample circuit, `x * y + x == z` with public `x` and `z`, for `x = 3` and
+/// `y = seed`, and saves the proof to `paths`, so the hybrid flow runs without zk-6358 proofs.
+pub fn generate_sample_proof<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+    paths: &P2ProofPaths,
+    seed: u64,
+) -> CookResult<()> {
+    let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+    let x = builder.add_virtual_target();
+    let y = builder.add_virtual_target();
+    let xy = builder.mul(x, y);
+    let z = builder.add(xy, x);
+    builder.register_public_input(x);
+    builder.register_public_input(z);
+    let data = builder.build::<C>();
 
-    let gate_serializer = DefaultGateSerializer;
-    let ccd = fs::read(ccd_path).unwrap();
-    let common = CommonCircuitData::<F, D>::from_bytes(ccd.clone(), &gate_serializer).unwrap();
+    let mut pw = PartialWitness::new();
+    pw.set_target(x, F::from_canonical_u64(3));
+    pw.set_target(y, F::from_canonical_u64(seed));
+    let proof = data
+        .prove(pw)
+        .map_err(|e| CookError::Verification(e.to_string()))?;
 
-    let ppis = fs::read(ppis_path).unwrap();
-    let ppis: ProofWithPublicInputs<F, C, D> = serde_json::from_slice(&ppis).unwrap();
+    save_p2_proof(paths, &proof, &data.verifier_data())
+}
+
+pub fn load_p2_proof
+<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
+(paths: &P2ProofPaths) -> CookResult<(ProofWithPublicInputs<F, C, D>, VerifierOnlyCircuitData<C, D>, Vec<u8>)>
+{
+    let verifier_only = read_verifier_only::<C, D>(paths)?;
+    let ccd = read_common_bytes(paths)?;
+    let common = decode_common::<F, D>(&ccd)?;
+
+    let ppis = fs::read(&paths.ppis)?;
+    let ppis: ProofWithPublicInputs<F, C, D> = serde_json::from_slice(&ppis)?;
     let vd = VerifierCircuitData {
         verifier_only,
         common,
     };
-    vd.verify(ppis.clone()).unwrap();
+    vd.verify(ppis.clone())
+        .map_err(|e| CookError::Verification(e.to_string()))?;
 
     Ok((ppis, vd.verifier_only, ccd))
 }
 
+/// The circuit digest cached in `P2ProofPaths::digest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedCircuitDigest {
+    /// `vod_hash` of the `vod` file the digest was decoded from
+    vod_hash: String,
+    circuit_digest: [u64; 4],
+}
+
+/// `sha256` of the `vod` file bytes, hex encoded.
+fn vod_hash(vod: &[u8]) -> String {
+    hex::encode(Sha256::digest(vod))
+}
+
+/// Caches `circuit_digest` in `paths.digest` for the `vod` bytes written to `paths.vod`.
+fn store_circuit_digest(paths: &P2ProofPaths, vod: &[u8], circuit_digest: [u64; 4]) -> CookResult<()> {
+    let cached = CachedCircuitDigest { vod_hash: vod_hash(vod), circuit_digest };
+    fs::write(&paths.digest, serde_json::to_string(&cached)?)?;
+    Ok(())
+}
+
+/// The circuit digest of the proof at `paths`, as canonical goldilocks elements.
+///
+/// Read from `paths.digest` if cached for the current `paths.vod`, otherwise decoded from
+/// `paths.vod`, so a replaced `vod` is never grouped under a stale digest. Nothing is written,
+/// the digest is only cached by `save_p2_proof`.
+pub fn circuit_digest<C: GenericConfig<D>, const D: usize>(paths: &P2ProofPaths) -> CookResult<[u64; 4]> {
+    let vod_hash = vod_hash(&fs::read(&paths.vod)?);
+    let cached = fs::read(&paths.digest)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<CachedCircuitDigest>(&bytes).ok());
+    if let Some(cached) = cached.filter(|cached| cached.vod_hash == vod_hash) {
+        return Ok(cached.circuit_digest);
+    }
+
+    let verifier_only = read_verifier_only::<C, D>(paths)?;
+    Ok(hash_elements(verifier_only.circuit_digest))
+}
+
+/// `0x` prefixed hex of the little-endian bytes of a circuit digest.
+pub fn circuit_digest_hex(digest: &[u64; 4]) -> String {
+    let bytes = digest.iter().flat_map(|e| e.to_le_bytes()).collect::<Vec<u8>>();
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Parses a circuit digest from `circuit_digest_hex`.
+pub fn parse_circuit_digest(digest_hex: &str) -> CookResult<[u64; 4]> {
+    let bytes = hex::decode(digest_hex.trim_start_matches("0x"))
+        .map_err(|e| CookError::Decode(format!("circuit digest: {}", e)))?;
+    if bytes.len() != 32 {
+        return Err(CookError::Decode(format!("circuit digest of {} bytes", bytes.len())));
+    }
+    Ok(std::array::from_fn(|i| u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().unwrap())))
+}
+
+/// The proofs of one plonky2 circuit, sharing its decoded verifier data.
+pub struct P2Circuit<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize> {
+    pub circuit_digest: [u64; 4],
+    pub verifier_data: VerifierCircuitData<F, C, D>,
+    /// The `CommonCircuitData` bytes, decoded once for all of `proofs`
+    pub common_bytes: Vec<u8>,
+    /// Every proof of the circuit, with its index in the order the proofs were added
+    pub proofs: Vec<(usize, ProofWithPublicInputs<F, C, D>)>,
+}
+
+/// Stored plonky2 proofs grouped by circuit digest, so the verifier data of every circuit is only
+/// loaded and decoded once.
+pub struct P2CircuitCache<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize> {
+    circuits: Vec<P2Circuit<F, C, D>>,
+    proof_count: usize,
+}
+
+impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize> Default for P2CircuitCache<F, C, D> {
+    fn default() -> Self {
+        P2CircuitCache { circuits: Vec::new(), proof_count: 0 }
+    }
+}
+
+impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize> P2CircuitCache<F, C, D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The circuits, in order of their first proof.
+    pub fn circuits(&self) -> &[P2Circuit<F, C, D>] {
+        &self.circuits
+    }
+
+    /// The number of proofs added to all the circuits.
+    pub fn proof_count(&self) -> usize {
+        self.proof_count
+    }
+
+    /// Loads and verifies the proof at `paths`, decoding its circuit data only if no proof of the
+    /// same circuit was added before.
+    pub fn add(&mut self, paths: &P2ProofPaths) -> CookResult<()> {
+        let circuit_digest = circuit_digest::<C, D>(paths)?;
+
+        let index = match self.circuits.iter().position(|c| c.circuit_digest == circuit_digest) {
+            Some(index) => index,
+            None => {
+                let verifier_only = read_verifier_only::<C, D>(paths)?;
+                let common_bytes = read_common_bytes(paths)?;
+                let common = decode_common::<F, D>(&common_bytes)?;
+
+                self.circuits.push(P2Circuit {
+                    circuit_digest,
+                    verifier_data: VerifierCircuitData { verifier_only, common },
+                    common_bytes,
+                    proofs: Vec::new(),
+                });
+                self.circuits.len() - 1
+            }
+        };
+
+        let ppis: ProofWithPublicInputs<F, C, D> = serde_json::from_slice(&fs::read(&paths.ppis)?)?;
+        let circuit = &mut self.circuits[index];
+        circuit
+            .verifier_data
+            .verify(ppis.clone())
+            .map_err(|e| CookError::Verification(e.to_string()))?;
+        circuit.proofs.push((self.proof_count, ppis));
+        self.proof_count += 1;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+    use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+    use plonky2_field::goldilocks_field::GoldilocksField;
 
     use super::*;
 
@@ -46,6 +361,90 @@ mod tests {
         type C = PoseidonGoldilocksConfig;
         type F = <C as GenericConfig<D>>::F;
 
-        let _ = load_p2_proof::<F, C, D>("8").unwrap();
+        let _ = load_p2_proof::<F, C, D>(&P2ProofPaths::in_dir(FRI_PROOF_DIR, "8")).unwrap();
+    }
+
+    #[test]
+    fn test_load_missing_p2_proof() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let result = load_p2_proof::<F, C, D>(&P2ProofPaths::in_dir(FRI_PROOF_DIR, "missing"));
+        assert!(matches!(result, Err(CookError::Io(_))));
+    }
+
+    #[test]
+    fn test_sample_proof_formats() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let dir = std::env::temp_dir().join("sp1-cook-p2-sample");
+        for format in [P2DataFormat::Plonky2, P2DataFormat::Json, P2DataFormat::Bincode] {
+            let paths = P2ProofPaths::in_dir_with_format(&dir, "sample", format);
+            generate_sample_proof::<F, C, D>(&paths, 5).unwrap();
+
+            let (ppis, vod, _) = load_p2_proof::<F, C, D>(&paths).unwrap();
+            assert_eq!(ppis.public_inputs, vec![F::from_canonical_u64(3), F::from_canonical_u64(18)]);
+            assert_eq!(circuit_digest::<C, D>(&paths).unwrap(), hash_elements(vod.circuit_digest));
+        }
+    }
+
+    #[test]
+    fn test_stale_circuit_digest() {
+        const D: usize = 2;
+        type F = GoldilocksField;
+
+        let dir = std::env::temp_dir().join("sp1-cook-p2-stale");
+        let paths = P2ProofPaths::in_dir(&dir, "replaced");
+        let other = P2ProofPaths::in_dir(&dir, "other");
+        generate_sample_proof::<F, PoseidonGoldilocksConfig, D>(&paths, 1).unwrap();
+        generate_sample_proof::<F, KeccakGoldilocksConfig, D>(&other, 1).unwrap();
+        let cached = circuit_digest::<PoseidonGoldilocksConfig, D>(&paths).unwrap();
+
+        // the vod is replaced without `save_p2_proof`, the cached digest is not used anymore
+        fs::copy(&other.vod, &paths.vod).unwrap();
+        let digest = circuit_digest::<KeccakGoldilocksConfig, D>(&paths).unwrap();
+        assert_ne!(digest, cached);
+        assert_eq!(digest, circuit_digest::<KeccakGoldilocksConfig, D>(&other).unwrap());
+
+        // reading the digest never caches it
+        fs::remove_file(&other.digest).unwrap();
+        assert_eq!(digest, circuit_digest::<KeccakGoldilocksConfig, D>(&other).unwrap());
+        assert!(!other.digest.exists());
+    }
+
+    #[test]
+    fn test_p2_config_tag() {
+        for config in [P2Config::Poseidon, P2Config::Keccak] {
+            assert_eq!(P2Config::from_tag(config.tag()), Some(config));
+        }
+        assert_eq!(P2Config::from_tag(2), None);
+    }
+
+    #[test]
+    fn test_circuit_digest_hex() {
+        let digest = [1u64, u64::MAX, 0, 0x0123456789abcdef];
+        assert_eq!(parse_circuit_digest(&circuit_digest_hex(&digest)).unwrap(), digest);
+        assert!(parse_circuit_digest("0x0102").is_err());
+    }
+
+    #[test]
+    fn test_p2_circuit_cache() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let paths = P2ProofPaths::in_dir(FRI_PROOF_DIR, "8");
+        let mut cache = P2CircuitCache::<F, C, D>::new();
+        cache.add(&paths).unwrap();
+        cache.add(&paths).unwrap();
+
+        // both proofs share one circuit
+        assert_eq!(cache.circuits().len(), 1);
+        assert_eq!(cache.proof_count(), 2);
+        assert_eq!(cache.circuits()[0].proofs.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(circuit_digest::<C, D>(&paths).unwrap(), cache.circuits()[0].circuit_digest);
     }
 }
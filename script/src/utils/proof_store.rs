@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{
+    HashableKey, ProverClient, SP1CompressedProof, SP1PlonkBn254Proof, SP1Proof, SP1Stdin,
+    SP1VerifyingKey,
+};
+
+use super::error::{CookError, CookResult};
+use super::runner::CookOutput;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// The kind of a stored proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofKind {
+    Core,
+    Compressed,
+    Plonk,
+}
+
+impl ProofKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofKind::Core => "core",
+            ProofKind::Compressed => "compressed",
+            ProofKind::Plonk => "plonk",
+        }
+    }
+}
+
+/// A manifest entry describing one stored proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactEntry {
+    pub id: String,
+    pub program: String,
+    /// `sha256(elf)`, hex encoded
+    pub elf_hash: String,
+    /// `vk.bytes32()`
+    pub vk_hash: String,
+    pub kind: ProofKind,
+    /// `stdin_digest(stdin)`, hex encoded
+    pub input_digest: String,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub cycles: Option<u64>,
+    /// The proof file, relative to the store root
+    pub file: String,
+}
+
+/// A directory of proofs, described by the `MANIFEST_FILE` in it.
+pub struct ProofStore {
+    root: PathBuf,
+    entries: Vec<ArtifactEntry>,
+}
+
+/// `sha256(elf)`, hex encoded.
+pub fn elf_hash(elf: &[u8]) -> String {
+    hex::encode(Sha256::digest(elf))
+}
+
+/// A sha256 over every buffer written to `stdin`, each prefixed by its length, then over the
+/// bincode of the proofs written to it, so the inputs of an aggregation are told apart.
+pub fn stdin_digest(stdin: &SP1Stdin) -> String {
+    let mut hasher = Sha256::new();
+    for buf in stdin.buffer.iter() {
+        hasher.update((buf.len() as u64).to_le_bytes());
+        hasher.update(buf);
+    }
+    hasher.update(bincode::serialize(&stdin.proofs).expect("serialize stdin proofs error"));
+    hex::encode(hasher.finalize())
+}
+
+impl ProofStore {
+    /// Opens the store at `root`, which is created on the first save.
+    pub fn open(root: impl Into<PathBuf>) -> CookResult<Self> {
+        let root = root.into();
+        let manifest_path = root.join(MANIFEST_FILE);
+        let entries = if manifest_path.exists() {
+            serde_json::from_slice(&std::fs::read(&manifest_path)?)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(ProofStore { root, entries })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn list(&self) -> &[ArtifactEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ArtifactEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// The most recently stored proof of `program` of the given kind.
+    pub fn latest(&self, program: &str, kind: ProofKind) -> Option<&ArtifactEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.program == program && entry.kind == kind)
+            .max_by_key(|entry| entry.timestamp)
+    }
+
+    /// Saves the proof in `output` and records it in the manifest.
+    pub fn save(
+        &mut self,
+        program: &str,
+        elf: &[u8],
+        vk: &SP1VerifyingKey,
+        input_digest: String,
+        output: &CookOutput,
+        cycles: Option<u64>,
+    ) -> CookResult<ArtifactEntry> {
+        let kind = match output {
+            CookOutput::Core(_) => ProofKind::Core,
+            CookOutput::Compressed(_) => ProofKind::Compressed,
+            CookOutput::Plonk(_) => ProofKind::Plonk,
+            CookOutput::Executed(..) => return Err(CookError::NoProof),
+        };
+
+        // milliseconds, so the proofs of the same input in the same second get different ids
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let timestamp = now.as_secs();
+        let id = format!("{}-{}-{}-{}", program, kind.as_str(), now.as_millis(), &input_digest[..8]);
+        let file = format!("{}.bin", id);
+
+        std::fs::create_dir_all(&self.root)?;
+        let proof_path = self.root.join(&file);
+        if self.get(&id).is_some() || proof_path.exists() {
+            return Err(CookError::AlreadyExists(id));
+        }
+        match output {
+            CookOutput::Core(proof) => proof.save(&proof_path),
+            CookOutput::Compressed(proof) => proof.save(&proof_path),
+            CookOutput::Plonk(proof) => proof.save(&proof_path),
+            CookOutput::Executed(..) => unreachable!(),
+        }
+        .map_err(|e| CookError::Proof(e.to_string()))?;
+
+        let entry = ArtifactEntry {
+            id,
+            program: program.to_string(),
+            elf_hash: elf_hash(elf),
+            vk_hash: vk.bytes32(),
+            kind,
+            input_digest,
+            timestamp,
+            cycles,
+            file,
+        };
+        self.entries.push(entry.clone());
+        self.write_manifest()?;
+
+        Ok(entry)
+    }
+
+    /// Loads the proof stored under `id`.
+    pub fn load(&self, id: &str) -> CookResult<CookOutput> {
+        let entry = self.get(id).ok_or_else(|| CookError::NotFound(id.to_string()))?;
+        let proof_path = self.root.join(&entry.file);
+
+        match entry.kind {
+            ProofKind::Core => SP1Proof::load(&proof_path).map(CookOutput::Core),
+            ProofKind::Compressed => SP1CompressedProof::load(&proof_path).map(CookOutput::Compressed),
+            ProofKind::Plonk => SP1PlonkBn254Proof::load(&proof_path).map(CookOutput::Plonk),
+        }
+        .map_err(|e| CookError::Proof(e.to_string()))
+    }
+
+    /// Loads the proof stored under `id` and verifies it against the program `elf`, which should
+    /// be the one it was proven with.
+    pub fn verify(&self, client: &ProverClient, id: &str, elf: &[u8]) -> CookResult<()> {
+        let entry = self.get(id).ok_or_else(|| CookError::NotFound(id.to_string()))?;
+        if entry.elf_hash != elf_hash(elf) {
+            return Err(CookError::ElfMismatch {
+                expected: entry.elf_hash.clone(),
+                found: elf_hash(elf),
+            });
+        }
+
+        let (_, vk) = client.setup(elf);
+        if entry.vk_hash != vk.bytes32() {
+            return Err(CookError::VkMismatch {
+                expected: entry.vk_hash.clone(),
+                found: vk.bytes32(),
+            });
+        }
+
+        match self.load(id)? {
+            CookOutput::Core(proof) => client.verify(&proof, &vk),
+            CookOutput::Compressed(proof) => client.verify_compressed(&proof, &vk),
+            CookOutput::Plonk(proof) => client.verify_plonk(&proof, &vk),
+            CookOutput::Executed(..) => unreachable!(),
+        }
+        .map_err(|e| CookError::Verification(e.to_string()))
+    }
+
+    fn write_manifest(&self) -> CookResult<()> {
+        std::fs::write(
+            self.root.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&self.entries)?,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stdin_digest() {
+        let mut a = SP1Stdin::new();
+        a.write_vec(vec![1, 2]);
+        a.write_vec(vec![3]);
+
+        let mut b = SP1Stdin::new();
+        b.write_vec(vec![1]);
+        b.write_vec(vec![2, 3]);
+
+        assert_ne!(stdin_digest(&a), stdin_digest(&b));
+        assert_eq!(stdin_digest(&a), stdin_digest(&a.clone()));
+    }
+
+    #[test]
+    fn test_load_unknown_id() {
+        // the store is only created on the first save
+        let store = ProofStore::open(std::env::temp_dir().join("sp1-cook-no-store")).expect("open proof store error");
+        assert!(matches!(store.load("fibonacci-core-0-00000000"), Err(CookError::NotFound(_))));
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sp1_core::runtime::{ExecutionReport, SyscallCode};
+use sp1_sdk::SP1PublicValues;
+use tracing::info;
+
+use super::fixtures::PROOF_PATH;
+
+/// The cycle and syscall counts of executing a cook, used to size batches before proving.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionSummary {
+    pub cook: String,
+    pub total_cycles: u64,
+    pub total_syscalls: u64,
+    pub keccak_permute: u64,
+    /// `SECP256K1_ADD`, `SECP256K1_DOUBLE` and `SECP256K1_DECOMPRESS` together
+    pub secp256k1_ops: u64,
+    pub verify_sp1_proof: u64,
+    /// Every syscall that was invoked at least once, by name
+    pub syscall_counts: BTreeMap<String, u64>,
+    pub public_values_len: usize,
+}
+
+impl ExecutionSummary {
+    pub fn from_report(cook: &str, report: &ExecutionReport, public_values: &SP1PublicValues) -> Self {
+        let count = |code: SyscallCode| report.syscall_counts.get(&code).copied().unwrap_or(0);
+
+        let syscall_counts = report
+            .syscall_counts
+            .iter()
+            .filter(|(_, n)| **n > 0)
+            .map(|(code, n)| (format!("{:?}", code), *n))
+            .collect();
+
+        ExecutionSummary {
+            cook: cook.to_string(),
+            total_cycles: report.total_instruction_count(),
+            total_syscalls: report.total_syscall_count(),
+            keccak_permute: count(SyscallCode::KECCAK_PERMUTE),
+            secp256k1_ops: count(SyscallCode::SECP256K1_ADD)
+                + count(SyscallCode::SECP256K1_DOUBLE)
+                + count(SyscallCode::SECP256K1_DECOMPRESS),
+            verify_sp1_proof: count(SyscallCode::VERIFY_SP1_PROOF),
+            syscall_counts,
+            public_values_len: public_values.as_slice().len(),
+        }
+    }
+
+    /// `{PROOF_PATH}{cook}-execution.json`
+    pub fn file(cook: &str) -> String {
+        format!("{}{}-execution.json", PROOF_PATH, cook)
+    }
+
+    /// Logs the summary and writes it next to the proof artifacts.
+    pub fn save_to_local(&self) {
+        let summary = serde_json::to_string_pretty(self).unwrap();
+        info!("execution report: {}", summary);
+
+        std::fs::create_dir_all(PROOF_PATH).expect("failed to create proof path");
+        std::fs::write(Self::file(&self.cook), summary).expect("failed to write execution report");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_summary() {
+        let mut report = ExecutionReport::default();
+        report.syscall_counts.insert(SyscallCode::KECCAK_PERMUTE, 24);
+        report.syscall_counts.insert(SyscallCode::SECP256K1_ADD, 3);
+        report.syscall_counts.insert(SyscallCode::SECP256K1_DOUBLE, 5);
+        report.syscall_counts.insert(SyscallCode::VERIFY_SP1_PROOF, 0);
+
+        let summary = ExecutionSummary::from_report("test", &report, &SP1PublicValues::from(&[1u8; 33]));
+
+        assert_eq!(summary.keccak_permute, 24);
+        assert_eq!(summary.secp256k1_ops, 8);
+        assert_eq!(summary.verify_sp1_proof, 0);
+        assert_eq!(summary.syscall_counts.len(), 3);
+        assert_eq!(summary.public_values_len, 33);
+    }
+}
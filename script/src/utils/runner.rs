@@ -0,0 +1,174 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use sp1_sdk::{
+    ProverClient, SP1CompressedProof, SP1PlonkBn254Proof, SP1Proof, SP1PublicValues,
+    SP1Stdin, SP1VerifyingKey,
+};
+use tracing::info;
+
+use super::error::CookResult;
+use super::fixtures::{CookPublicValues, FixtureBuilder, SP1ProofFixture, FIXTURE_PATH, PROOF_PATH};
+use super::proof_store::{stdin_digest, ProofKind, ProofStore};
+use super::report::ExecutionSummary;
+
+/// How far a cook is taken: executed only, or proven up to the given proof kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProveMode {
+    /// Execute the program without proving.
+    Execute,
+    /// Generate a core (sharded STARK) proof.
+    Core,
+    /// Generate a compressed proof, which can be verified by `verify_sp1_proof`.
+    Compressed,
+    /// Generate a PLONK proof over BN254, which can be verified on-chain.
+    Plonk,
+}
+
+impl ProveMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProveMode::Execute => "execute",
+            ProveMode::Core => "core",
+            ProveMode::Compressed => "compressed",
+            ProveMode::Plonk => "plonk",
+        }
+    }
+}
+
+/// How a cook is run.
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    pub mode: ProveMode,
+    /// Execute the program before proving it, to check its public values and record its cycles
+    /// first. Always true in `ProveMode::Execute`.
+    pub execute_first: bool,
+}
+
+impl RunOptions {
+    fn executes(&self) -> bool {
+        self.mode == ProveMode::Execute || self.execute_first
+    }
+}
+
+/// The result of running a cook in some `ProveMode`.
+pub enum CookOutput {
+    Executed(SP1PublicValues, ExecutionSummary),
+    Core(SP1Proof),
+    Compressed(SP1CompressedProof),
+    Plonk(SP1PlonkBn254Proof),
+}
+
+impl CookOutput {
+    pub fn public_values(&self) -> &SP1PublicValues {
+        match self {
+            CookOutput::Executed(public_values, _) => public_values,
+            CookOutput::Core(proof) => &proof.public_values,
+            CookOutput::Compressed(proof) => &proof.public_values,
+            CookOutput::Plonk(proof) => &proof.public_values,
+        }
+    }
+}
+
+/// `{name}-fixture.json` for PLONK proofs, which the Foundry tests load, and
+/// `{name}-{kind}-fixture.json` for the others, relative to `FIXTURE_PATH`
+pub fn fixture_file(name: &str, kind: ProofKind) -> String {
+    match kind {
+        ProofKind::Plonk => format!("{}-fixture.json", name),
+        _ => format!("{}-{}-fixture.json", name, kind.as_str()),
+    }
+}
+
+/// Runs `elf` over `stdin` with the given options, returning the output and, when proving, the
+/// verifying key.
+///
+/// In `ProveMode::Execute`, or with `execute_first`, the program is executed first, writing an
+/// `ExecutionSummary` to `ExecutionSummary::file(name)`. Every generated proof is then verified
+/// and saved to the `ProofStore` at `PROOF_PATH`, along with its cycle count if executed, and put
+/// into a fixture in `fixture_file(name, kind)` with its public values decoded as `D`.
+pub fn run_cook<D: CookPublicValues>(
+    client: &ProverClient,
+    name: &str,
+    elf: &[u8],
+    stdin: SP1Stdin,
+    options: RunOptions,
+) -> (CookOutput, Option<SP1VerifyingKey>) {
+    run_cook_checked::<D>(client, name, elf, stdin, options, |_| Ok(()))
+}
+
+/// `run_cook`, which also checks the decoded public values before any proof or fixture is saved:
+/// those of the execution if executed first, else those of the proof.
+pub fn run_cook_checked<D: CookPublicValues>(
+    client: &ProverClient,
+    name: &str,
+    elf: &[u8],
+    stdin: SP1Stdin,
+    options: RunOptions,
+    check: impl FnOnce(&D) -> CookResult<()>,
+) -> (CookOutput, Option<SP1VerifyingKey>) {
+    let input_digest = stdin_digest(&stdin);
+    let mode = options.mode;
+
+    let (summary, check) = if options.executes() {
+        let (public_values, report) = client.execute(elf, stdin.clone()).expect("failed to execute program");
+        info!("successfully executed {}", name);
+        check(&D::decode(public_values.as_slice())).expect("public values check failed");
+
+        let summary = ExecutionSummary::from_report(name, &report, &public_values);
+        summary.save_to_local();
+        if mode == ProveMode::Execute {
+            return (CookOutput::Executed(public_values, summary), None);
+        }
+        (Some(summary), None)
+    } else {
+        (None, Some(check))
+    };
+
+    // Setup the program.
+    let (pk, vk) = client.setup(elf);
+
+    let output = match mode {
+        ProveMode::Core => {
+            let proof = client.prove(&pk, stdin).expect("failed to generate proof");
+            client.verify(&proof, &vk).expect("failed to verify proof");
+            CookOutput::Core(proof)
+        }
+        ProveMode::Compressed => {
+            let proof = client
+                .prove_compressed(&pk, stdin)
+                .expect("failed to generate proof");
+            client
+                .verify_compressed(&proof, &vk)
+                .expect("failed to verify proof");
+            CookOutput::Compressed(proof)
+        }
+        ProveMode::Plonk => {
+            let proof = client.prove_plonk(&pk, stdin).expect("failed to generate proof");
+            client.verify_plonk(&proof, &vk).expect("verification failed");
+            CookOutput::Plonk(proof)
+        }
+        ProveMode::Execute => unreachable!(),
+    };
+
+    if let Some(check) = check {
+        check(&D::decode(output.public_values().as_slice())).expect("public values check failed");
+    }
+
+    let mut store = ProofStore::open(PROOF_PATH).expect("open proof store error");
+    let entry = store
+        .save(name, elf, &vk, input_digest, &output, summary.map(|summary| summary.total_cycles))
+        .expect("saving proof failed");
+    info!("proof stored as {}", entry.id);
+
+    let fixture_path = Path::new(FIXTURE_PATH).join(fixture_file(name, entry.kind));
+    let fixture = match &output {
+        CookOutput::Core(proof) => SP1ProofFixture::<D>::from_proof_vk(proof, &vk),
+        CookOutput::Compressed(proof) => SP1ProofFixture::<D>::from_proof_vk(proof, &vk),
+        CookOutput::Plonk(proof) => SP1ProofFixture::<D>::from_proof_vk(proof, &vk),
+        CookOutput::Executed(..) => unreachable!(),
+    };
+    fixture.save_to_local(&fixture_path).expect("failed to write fixture");
+
+    info!("successfully generated and verified {} proof for {}!", mode.as_str(), name);
+    (output, Some(vk))
+}
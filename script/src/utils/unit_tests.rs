@@ -2,6 +2,7 @@ use std::ops::AddAssign;
 
 use k256::{ecdsa::{signature::hazmat::PrehashVerifier, Signature, SigningKey, VerifyingKey}, elliptic_curve::generic_array::sequence::Lengthen};
 use rand::{rngs::OsRng, Rng};
+use tiny_keccak::{Hasher, Keccak};
 use tracing::info;
 use zk_6358::utils6358::{deploy_tx::{BaseAsset, DeployTransaction}, mint_tx::MintTransaction, transaction::{generate_rand_input, generate_rand_output, GasFeeTransaction, SpendTransaction, TransactionInput, TransactionOutput}, tx_eip_712::EIP712DataHashing, utxo::{AMOUNT_LEN, TOKEN_ADDRESS_LEN, USER_ADDRESS_LEN}};
 use itertools::Itertools;
@@ -402,10 +403,25 @@ pub fn sp1_sign_message_recoverable(msg_digest: &[u8; HASH_LEN], sk: &SigningKey
     sig_recoverable.0.to_bytes().append(sig_recoverable.1.to_byte()).to_vec().try_into().unwrap()
 }
 
+/// The EIP-712 digest of `tx` in the domain of `domain_separator`, as hashed by the `sp1eip712`
+/// guest, rather than in the fixed domain of `EIP712DataHashing::eip_712_hash`.
+pub fn sp1_eip_712_hash<T: EIP712DataHashing>(tx: &T, domain_separator: &[u8; HASH_LEN]) -> [u8; HASH_LEN] {
+    let mut hasher = Keccak::v256();
+    hasher.update(b"\x19\x01");
+    hasher.update(domain_separator);
+    hasher.update(&tx.data_hash());
+    let mut digest = [0u8; HASH_LEN];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+/// Generates a deploy, a mint and two spend transactions, signed in the domain of
+/// `domain_separator`.
 pub fn sp1_test_generate_a_batch(
     sk: ECDSASecretKey<EC>,
     x_le_bytes: [u8; USER_ADDRESS_LEN],
     y_le_bytes: [u8; USER_ADDRESS_LEN],
+    domain_separator: &[u8; HASH_LEN],
 ) -> Vec<SP1SignedOmniverseTx> {
     type F = GoldilocksField;
     let total_supply: u64 = 21000000;
@@ -423,7 +439,7 @@ pub fn sp1_test_generate_a_batch(
     deploy_tx.base_asset_data.per_mint_price_le =
         biguint_to_fixed_bytes_le::<AMOUNT_LEN>(&BigUint::from_u64(per_mint_price).unwrap());
 
-    let es_deploy_hash_value = deploy_tx.eip_712_hash();
+    let es_deploy_hash_value = sp1_eip_712_hash(&deploy_tx, domain_separator);
     // info!("hash outside: {:?}", es_deploy_hash_value);
 
     let sign_key = sk_to_signing_key(&sk);
@@ -448,7 +464,7 @@ pub fn sp1_test_generate_a_batch(
             per_mint,
             per_mint_price,
         );
-        let es_mint_hash_value = mint_tx.eip_712_hash();
+        let es_mint_hash_value = sp1_eip_712_hash(&mint_tx, domain_separator);
         let sig_bytes = sp1_sign_message_recoverable(&es_mint_hash_value, &sign_key);
 
         signed_omni_tx_vec.push(SP1SignedOmniverseTx::OmniMintTx(
@@ -493,7 +509,7 @@ pub fn sp1_test_generate_a_batch(
             },
         };
 
-        let es_spend_hash_value = spend_tx.eip_712_hash();
+        let es_spend_hash_value = sp1_eip_712_hash(&spend_tx, domain_separator);
         let sig_bytes = sp1_sign_message_recoverable(&es_spend_hash_value, &sign_key);
 
         signed_omni_tx_vec.push(SP1SignedOmniverseTx::OmniSpendTx(
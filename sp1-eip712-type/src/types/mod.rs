@@ -1 +1,2 @@
+pub mod sp1_domain;
 pub mod sp1_tx_types;
\ No newline at end of file
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+use zk_6358::utils6358::utxo::HASH_LEN;
+
+pub const ETH_ADDRESS_LEN: usize = 20;
+
+/// The EIP-712 type of `SP1EIP712Domain`, whose keccak256 is the domain type hash.
+pub const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// The EIP-712 domain of one deployment of the omniverse contracts, read by the `sp1eip712` guest
+/// before the transactions, so the signatures of any chain can be verified.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SP1EIP712Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub verifying_contract: [u8; ETH_ADDRESS_LEN],
+}
+
+fn keccak256(input: &[u8]) -> [u8; HASH_LEN] {
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    let mut output = [0u8; HASH_LEN];
+    hasher.finalize(&mut output);
+    output
+}
+
+impl SP1EIP712Domain {
+    /// The domain separator, `hashStruct(domain)`, which prefixes every EIP-712 digest signed for
+    /// this domain.
+    pub fn separator(&self) -> [u8; HASH_LEN] {
+        let mut data_bytes = keccak256(EIP712_DOMAIN_TYPE.as_bytes()).to_vec();
+
+        data_bytes.append(&mut keccak256(self.name.as_bytes()).to_vec());
+        data_bytes.append(&mut keccak256(self.version.as_bytes()).to_vec());
+
+        // uint256 chain id
+        let mut chain_id = [0u8; 32];
+        chain_id[32 - 8..].copy_from_slice(&self.chain_id.to_be_bytes());
+        data_bytes.append(&mut chain_id.to_vec());
+
+        // address, left padded
+        let mut verifying_contract = [0u8; 32];
+        verifying_contract[32 - ETH_ADDRESS_LEN..].copy_from_slice(&self.verifying_contract);
+        data_bytes.append(&mut verifying_contract.to_vec());
+
+        keccak256(&data_bytes)
+    }
+}
+
+/// The domain the `sp1eip712` guest reads before the transactions: either the fields of an
+/// `SP1EIP712Domain`, hashed in the guest, or a raw domain separator, such as the
+/// `EIP712_DOMAIN_HASH` the transactions were signed with before the domain was configurable.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SP1EIP712DomainInput {
+    Domain(SP1EIP712Domain),
+    Separator([u8; HASH_LEN]),
+}
+
+impl SP1EIP712DomainInput {
+    /// The separator of the domain, which the guest commits.
+    pub fn separator(&self) -> [u8; HASH_LEN] {
+        match self {
+            SP1EIP712DomainInput::Domain(domain) => domain.separator(),
+            SP1EIP712DomainInput::Separator(separator) => *separator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_separator() {
+        // the default domain of `sp1-cook eip712`
+        let domain = SP1EIP712Domain {
+            name: "Omniverse Transaction".to_string(),
+            version: "1".to_string(),
+            chain_id: 1,
+            verifying_contract: [0u8; ETH_ADDRESS_LEN],
+        };
+        let expected = [
+            88, 2, 33, 122, 142, 170, 166, 113, 60, 67, 22, 42, 223, 187, 119, 78, 251, 158, 254, 122, 65, 99, 188, 197,
+            92, 134, 2, 10, 244, 221, 245, 18,
+        ];
+        assert_eq!(domain.separator(), expected);
+        assert_eq!(SP1EIP712DomainInput::Domain(domain).separator(), expected);
+        assert_eq!(SP1EIP712DomainInput::Separator([7u8; HASH_LEN]).separator(), [7u8; HASH_LEN]);
+    }
+}
@@ -14,38 +14,83 @@ pub enum SP1SignedOmniverseTx {
     InvalidTx,
 }
 
+/// Whether a transaction of the batch is valid, committed with its txid by the `sp1eip712` guest
+/// when it reads the batch with verdicts, instead of aborting the whole proof.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SP1TxVerdict {
+    Valid = 0,
+    /// `SP1SignedOmniverseTx::InvalidTx`, which has no txid either
+    InvalidVariant = 1,
+    /// no public key can be recovered from the signature and the EIP-712 digest
+    UnrecoverableSignature = 2,
+    /// the recovered public key is not the one of the transaction
+    PkMismatch = 3,
+}
+
+impl SP1TxVerdict {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(SP1TxVerdict::Valid),
+            1 => Some(SP1TxVerdict::InvalidVariant),
+            2 => Some(SP1TxVerdict::UnrecoverableSignature),
+            3 => Some(SP1TxVerdict::PkMismatch),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SP1TxVerdict::Valid => "valid",
+            SP1TxVerdict::InvalidVariant => "invalid-variant",
+            SP1TxVerdict::UnrecoverableSignature => "unrecoverable-signature",
+            SP1TxVerdict::PkMismatch => "pk-mismatch",
+        }
+    }
+}
+
 impl SP1SignedOmniverseTx {
     pub fn full_pk_be(&self) -> [u8; SP1_FULL_PK_LEN] {
+        self.try_full_pk_be().expect("invalid transaction")
+    }
+
+    pub fn get_sig_be(&self) -> [u8; SIGN_BYTES] {
+        self.try_get_sig_be().expect("invalid transaction")
+    }
+
+    /// `full_pk_be`, or `None` for an `InvalidTx`.
+    pub fn try_full_pk_be(&self) -> Option<[u8; SP1_FULL_PK_LEN]> {
         match self {
             SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) => {
-                signed_deploy_tx.full_pk_be()
+                Some(signed_deploy_tx.full_pk_be())
             },
             SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => {
-                signed_mint_tx.full_pk_be()
+                Some(signed_mint_tx.full_pk_be())
             },
             SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => {
-                signed_spend_tx.full_pk_be()
+                Some(signed_spend_tx.full_pk_be())
             },
-            _ => {
-                panic!("invalid transaction")
-            }
+            SP1SignedOmniverseTx::InvalidTx => None,
         }
     }
 
-    pub fn get_sig_be(&self) -> [u8; SIGN_BYTES] {
+    /// `get_sig_be`, or `None` for an `InvalidTx`.
+    pub fn try_get_sig_be(&self) -> Option<[u8; SIGN_BYTES]> {
         match self {
             SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) => {
-                signed_deploy_tx.signature_be()
+                Some(signed_deploy_tx.signature_be())
             },
             SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => {
-                signed_mint_tx.signature_be()
+                Some(signed_mint_tx.signature_be())
             },
             SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => {
-                signed_spend_tx.signature_be()
+                Some(signed_spend_tx.signature_be())
             },
-            _ => {
-                panic!("invalid transaction")
-            }
+            SP1SignedOmniverseTx::InvalidTx => None,
         }
     }
 }
@@ -0,0 +1 @@
+pub mod types;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The AIRs the `p3agg` program can verify, named by the tag written before the proof.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum P3ChipId {
+    /// `Poseidon2Chip`
+    Poseidon2 = 0,
+    /// `Poseidon2WideChip<3>`
+    Poseidon2Wide3 = 1,
+    /// `Poseidon2WideChip<7>`
+    Poseidon2Wide7 = 2,
+}
+
+impl P3ChipId {
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(P3ChipId::Poseidon2),
+            1 => Some(P3ChipId::Poseidon2Wide3),
+            2 => Some(P3ChipId::Poseidon2Wide7),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            P3ChipId::Poseidon2 => "poseidon2",
+            P3ChipId::Poseidon2Wide3 => "poseidon2-wide-3",
+            P3ChipId::Poseidon2Wide7 => "poseidon2-wide-7",
+        }
+    }
+}
+
+impl fmt::Display for P3ChipId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for P3ChipId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [P3ChipId::Poseidon2, P3ChipId::Poseidon2Wide3, P3ChipId::Poseidon2Wide7]
+            .into_iter()
+            .find(|id| id.as_str() == s)
+            .ok_or_else(|| format!("unknown p3 chip: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chip_id_names() {
+        for id in [P3ChipId::Poseidon2, P3ChipId::Poseidon2Wide3, P3ChipId::Poseidon2Wide7] {
+            assert_eq!(P3ChipId::from_tag(id.tag()), Some(id));
+            assert_eq!(id.to_string().parse::<P3ChipId>(), Ok(id));
+        }
+    }
+}
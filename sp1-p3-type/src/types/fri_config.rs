@@ -0,0 +1,113 @@
+use p3_baby_bear::{BabyBear, DiffusionMatrixBabyBear};
+use p3_challenger::DuplexChallenger;
+use p3_commit::ExtensionMmcs;
+use p3_dft::Radix2DitParallel;
+use p3_field::{extension::BinomialExtensionField, Field};
+use p3_fri::{FriConfig, TwoAdicFriPcs};
+use p3_merkle_tree::FieldMerkleTreeMmcs;
+use p3_poseidon2::{Poseidon2, Poseidon2ExternalMatrixGeneral};
+use p3_symmetric::{PaddingFreeSponge, TruncatedPermutation};
+use p3_uni_stark::{Proof, StarkConfig};
+use serde::{Deserialize, Serialize};
+use sp1_core::utils::inner_perm;
+
+type Val = BabyBear;
+type Challenge = BinomialExtensionField<Val, 4>;
+type Perm = Poseidon2<Val, Poseidon2ExternalMatrixGeneral, DiffusionMatrixBabyBear, 16, 7>;
+type MyHash = PaddingFreeSponge<Perm, 16, 8, 8>;
+type MyCompress = TruncatedPermutation<Perm, 2, 8, 16>;
+type ValMmcs = FieldMerkleTreeMmcs<<Val as Field>::Packing, <Val as Field>::Packing, MyHash, MyCompress, 8>;
+type ChallengeMmcs = ExtensionMmcs<Val, Challenge, ValMmcs>;
+type Dft = Radix2DitParallel;
+type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
+
+pub type P3Challenger = DuplexChallenger<Val, Perm, 16, 8>;
+
+/// The BabyBear Poseidon2 uni-STARK config of `BabyBearPoseidon2`, with any FRI parameters.
+pub type P3StarkConfig = StarkConfig<Pcs, Challenge, P3Challenger>;
+
+/// The FRI parameters a p3 proof is made with, which its verifier has to use too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriParams {
+    pub log_blowup: usize,
+    pub num_queries: usize,
+    pub proof_of_work_bits: usize,
+}
+
+impl FriParams {
+    /// The least conjectured security, in bits, that a proof is made or verified with.
+    pub const MIN_SECURITY_BITS: usize = 100;
+
+    /// As in `BabyBearPoseidon2::new()`: a fast prover and large proofs.
+    pub const DEFAULT: FriParams = FriParams {
+        log_blowup: 1,
+        num_queries: 100,
+        proof_of_work_bits: 16,
+    };
+
+    /// As in `BabyBearPoseidon2::compressed()`: a slower prover and fewer queries to verify.
+    pub const COMPRESSED: FriParams = FriParams {
+        log_blowup: 3,
+        num_queries: 33,
+        proof_of_work_bits: 16,
+    };
+
+    /// The conjectured security of these parameters in bits,
+    /// `log_blowup * num_queries + proof_of_work_bits`.
+    pub fn security_bits(&self) -> usize {
+        self.log_blowup * self.num_queries + self.proof_of_work_bits
+    }
+
+    /// Whether these parameters reach `MIN_SECURITY_BITS`.
+    pub fn is_secure(&self) -> bool {
+        self.security_bits() >= Self::MIN_SECURITY_BITS
+    }
+
+    pub fn stark_config(&self) -> P3StarkConfig {
+        let perm = inner_perm();
+        let hash = MyHash::new(perm.clone());
+        let compress = MyCompress::new(perm);
+        let val_mmcs = ValMmcs::new(hash, compress);
+        let challenge_mmcs = ChallengeMmcs::new(val_mmcs.clone());
+        let fri_config = FriConfig {
+            log_blowup: self.log_blowup,
+            num_queries: self.num_queries,
+            proof_of_work_bits: self.proof_of_work_bits,
+            mmcs: challenge_mmcs,
+        };
+        let pcs = Pcs::new(27, Dft::default(), val_mmcs, fri_config);
+
+        P3StarkConfig::new(pcs)
+    }
+
+    pub fn challenger(&self) -> P3Challenger {
+        P3Challenger::new(inner_perm())
+    }
+}
+
+/// A p3 uni-STARK proof, carrying the FRI parameters it is verified with.
+#[derive(Serialize, Deserialize)]
+pub struct P3Proof {
+    pub fri: FriParams,
+    pub proof: Proof<P3StarkConfig>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_bits() {
+        assert_eq!(FriParams::DEFAULT.security_bits(), 116);
+        assert_eq!(FriParams::COMPRESSED.security_bits(), 115);
+        assert!(FriParams::DEFAULT.is_secure());
+        assert!(FriParams::COMPRESSED.is_secure());
+
+        let weak = FriParams {
+            log_blowup: 1,
+            num_queries: 50,
+            proof_of_work_bits: 16,
+        };
+        assert!(!weak.is_secure());
+    }
+}
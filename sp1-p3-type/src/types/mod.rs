@@ -0,0 +1,2 @@
+pub mod chip_id;
+pub mod fri_config;
\ No newline at end of file
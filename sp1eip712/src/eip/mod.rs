@@ -1,3 +1,6 @@
 pub mod sp1_tx_eip_712;
 pub mod sp1_eip712_spend;
-pub mod traits;
\ No newline at end of file
+pub mod sp1_eip712_types;
+pub mod traits;
+pub mod tx_record;
+pub mod txid_merkle;
\ No newline at end of file
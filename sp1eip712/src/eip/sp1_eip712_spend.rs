@@ -1,11 +1,15 @@
+use std::sync::OnceLock;
+
 use zk_6358::{prelude::ZK6358GoldilocksField, utils6358::{transaction::SpendTransaction, type_utils::ZK6358DataHashing, utxo::HASH_LEN}};
 
+use super::sp1_eip712_types::TRANSFER_TYPE;
 use super::sp1_tx_eip_712::{sp1_raw_bytes_keccak256_hash, SP1EIP712DataHashing, SP1TxIdHashing};
 
 
 impl SP1EIP712DataHashing for SpendTransaction {
-    fn type_hash() -> [u8; zk_6358::utils6358::utxo::HASH_LEN] {
-        [225, 13, 10, 205, 160, 64, 111, 241, 213, 202, 142, 72, 73, 146, 196, 250, 232, 135, 196, 23, 101, 16, 141, 223, 54, 97, 115, 52, 42, 183, 194, 119]
+    fn type_hash() -> [u8; HASH_LEN] {
+        static TYPE_HASH: OnceLock<[u8; HASH_LEN]> = OnceLock::new();
+        *TYPE_HASH.get_or_init(|| TRANSFER_TYPE.type_hash())
     }
 
     fn data_bytes(&self) -> Vec<u8> {
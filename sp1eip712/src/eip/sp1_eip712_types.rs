@@ -0,0 +1,178 @@
+use zk_6358::utils6358::utxo::HASH_LEN;
+
+use super::sp1_tx_eip_712::sp1_raw_bytes_keccak256_hash;
+
+///////////////////////////////////////////////////////////////////////
+/// EIP-712 struct definitions
+pub struct EIP712Field {
+    pub ty: &'static str,
+    pub name: &'static str,
+}
+
+/// An EIP-712 struct type, declared field by field in the order of the Solidity struct.
+pub struct EIP712Struct {
+    pub name: &'static str,
+    pub fields: &'static [EIP712Field],
+}
+
+const fn field(ty: &'static str, name: &'static str) -> EIP712Field {
+    EIP712Field { ty, name }
+}
+
+pub const INPUT_TYPE: EIP712Struct = EIP712Struct {
+    name: "Input",
+    fields: &[
+        field("bytes32", "txid"),
+        field("uint32", "index"),
+        field("uint128", "amount"),
+        field("bytes32", "address"),
+    ],
+};
+
+pub const OUTPUT_TYPE: EIP712Struct = EIP712Struct {
+    name: "Output",
+    fields: &[field("uint128", "amount"), field("bytes32", "address")],
+};
+
+pub const DEPLOY_TYPE: EIP712Struct = EIP712Struct {
+    name: "Deploy",
+    fields: &[
+        field("bytes8", "salt"),
+        field("string", "name"),
+        field("bytes32", "deployer"),
+        field("uint128", "limit"),
+        field("uint128", "price"),
+        field("uint128", "total_supply"),
+        field("Input[]", "fee_inputs"),
+        field("Output[]", "fee_outputs"),
+    ],
+};
+
+pub const MINT_TYPE: EIP712Struct = EIP712Struct {
+    name: "Mint",
+    fields: &[
+        field("bytes32", "asset_id"),
+        field("Output[]", "outputs"),
+        field("Input[]", "fee_inputs"),
+        field("Output[]", "fee_outputs"),
+    ],
+};
+
+pub const TRANSFER_TYPE: EIP712Struct = EIP712Struct {
+    name: "Transfer",
+    fields: &[
+        field("bytes32", "asset_id"),
+        field("Input[]", "inputs"),
+        field("Output[]", "outputs"),
+        field("Input[]", "fee_inputs"),
+        field("Output[]", "fee_outputs"),
+    ],
+};
+
+/// Every struct type an omniverse transaction may reference.
+pub const OMNIVERSE_TYPES: &[&EIP712Struct] = &[&INPUT_TYPE, &OUTPUT_TYPE, &DEPLOY_TYPE, &MINT_TYPE, &TRANSFER_TYPE];
+
+///////////////////////////////////////////////////////////////////////
+/// type encoding
+impl EIP712Struct {
+    /// `Name(type1 name1,type2 name2,...)`, without the referenced types.
+    fn encode_own_type(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|f| format!("{} {}", f.ty, f.name))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}({})", self.name, fields)
+    }
+
+    /// The names of the struct types in `types` referenced by this one, directly or not.
+    fn referenced_types(&self, types: &[&EIP712Struct], found: &mut Vec<&'static str>) {
+        for f in self.fields {
+            // `Input[]` and `Input[2]` reference `Input`
+            let base = f.ty.split('[').next().unwrap_or(f.ty);
+            if found.contains(&base) || base == self.name {
+                continue;
+            }
+
+            if let Some(referenced) = types.iter().find(|t| t.name == base) {
+                found.push(referenced.name);
+                referenced.referenced_types(types, found);
+            }
+        }
+    }
+
+    /// `encodeType` of EIP-712: this type, then the types it references sorted by name.
+    pub fn encode_type(&self, types: &[&EIP712Struct]) -> String {
+        let mut referenced = Vec::new();
+        self.referenced_types(types, &mut referenced);
+        referenced.sort();
+
+        let mut encoded = self.encode_own_type();
+        referenced.iter().for_each(|name| {
+            let t = types.iter().find(|t| t.name == *name).expect("referenced type");
+            encoded.push_str(&t.encode_own_type());
+        });
+
+        encoded
+    }
+
+    /// `keccak256(encodeType(self))`, with the referenced types looked up in `OMNIVERSE_TYPES`.
+    pub fn type_hash(&self) -> [u8; HASH_LEN] {
+        sp1_raw_bytes_keccak256_hash(self.encode_type(OMNIVERSE_TYPES).as_bytes())
+    }
+}
+
+///////////////////////////////////////////////////////
+/// test
+#[cfg(test)]
+mod tests {
+    use zk_6358::utils6358::{deploy_tx::DeployTransaction, mint_tx::MintTransaction, transaction::{SpendTransaction, TransactionInput, TransactionOutput}};
+
+    use crate::eip::sp1_tx_eip_712::SP1EIP712DataHashing;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_type() {
+        assert_eq!(
+            MINT_TYPE.encode_type(OMNIVERSE_TYPES),
+            "Mint(bytes32 asset_id,Output[] outputs,Input[] fee_inputs,Output[] fee_outputs)Input(bytes32 txid,uint32 index,uint128 amount,bytes32 address)Output(uint128 amount,bytes32 address)"
+        );
+        assert_eq!(OUTPUT_TYPE.encode_type(OMNIVERSE_TYPES), "Output(uint128 amount,bytes32 address)");
+    }
+
+    #[test]
+    fn test_type_hashes() {
+        // the type hashes hard-coded before they were derived from the struct definitions
+        assert_eq!(
+            <TransactionInput as SP1EIP712DataHashing>::type_hash(),
+            [
+                119, 84, 107, 54, 3, 160, 139, 205, 137, 18, 192, 2, 17, 139, 145, 206, 79, 30, 17,
+                215, 149, 167, 0, 209, 0, 34, 102, 130, 225, 172, 138, 53,
+            ]
+        );
+        assert_eq!(
+            <TransactionOutput as SP1EIP712DataHashing>::type_hash(),
+            [
+                69, 230, 189, 249, 100, 29, 81, 134, 15, 124, 243, 126, 55, 37, 82, 207, 128, 30, 120,
+                162, 115, 136, 26, 187, 55, 232, 245, 171, 182, 105, 236, 166,
+            ]
+        );
+        assert_eq!(
+            <DeployTransaction as SP1EIP712DataHashing>::type_hash(),
+            [
+                87, 77, 50, 27, 163, 92, 214, 116, 160, 111, 168, 75, 75, 147, 217, 141, 104, 231, 20,
+                57, 63, 132, 190, 90, 112, 200, 137, 196, 152, 105, 73, 22,
+            ]
+        );
+        assert_eq!(
+            <MintTransaction as SP1EIP712DataHashing>::type_hash(),
+            [65, 248, 17, 248, 4, 254, 170, 184, 160, 96, 46, 26, 213, 54, 185, 120, 54, 63, 142, 160, 150, 182, 62, 166, 175, 18, 168, 44, 71, 254, 251, 202]
+        );
+        assert_eq!(
+            <SpendTransaction as SP1EIP712DataHashing>::type_hash(),
+            [225, 13, 10, 205, 160, 64, 111, 241, 213, 202, 142, 72, 73, 146, 196, 250, 232, 135, 196, 23, 101, 16, 141, 223, 54, 97, 115, 52, 42, 183, 194, 119]
+        );
+    }
+}
@@ -1,10 +1,14 @@
 
+use std::sync::OnceLock;
+
 use zk_6358::{prelude::ZK6358GoldilocksField, utils6358::{
     deploy_tx::DeployTransaction, mint_tx::MintTransaction, transaction::{TransactionInput, TransactionOutput}, type_utils::ZK6358DataHashing, utxo::HASH_LEN
 }};
 
 use tiny_keccak::{Hasher, Keccak};
 
+use super::sp1_eip712_types::{DEPLOY_TYPE, INPUT_TYPE, MINT_TYPE, OUTPUT_TYPE};
+
 ///////////////////////////////////////////////////////////////////////
 /// sp1
 pub fn sp1_raw_bytes_keccak256_hash(input: &[u8]) -> [u8; HASH_LEN] {
@@ -18,6 +22,8 @@ pub fn sp1_raw_bytes_keccak256_hash(input: &[u8]) -> [u8; HASH_LEN] {
 ///////////////////////////////////////////////////////////////////////
 // data
 
+/// The domain separator the `zk_6358` hashing is fixed to, which the guest now reads as an
+/// `SP1EIP712DomainInput` instead.
 pub const EIP712_DOMAIN_HASH: [u8; HASH_LEN] = [155, 31, 74, 110, 223, 92, 237, 247, 93, 153, 248, 164, 58, 122, 143, 141, 24, 237, 186, 65, 16, 147, 52, 93, 140, 150, 92, 47, 136, 159, 27, 23];
 
 pub const BYTES_UNIT_LEN: usize = 32;
@@ -38,17 +44,19 @@ pub trait SP1EIP712DataHashing {
         sp1_raw_bytes_keccak256_hash(&self.data_bytes())
     }
 
-    fn eip_712_bytes(&self) -> Vec<u8> {
+    /// `\x19\x01 || domain_separator || hashStruct(self)`, where `domain_separator` is
+    /// `SP1EIP712DomainInput::separator`.
+    fn eip_712_bytes(&self, domain_separator: &[u8; HASH_LEN]) -> Vec<u8> {
         let prefix = (b"\x19\x01").to_vec();
-        [&prefix[..], &EIP712_DOMAIN_HASH, &self.data_hash()].concat()
+        [&prefix[..], domain_separator, &self.data_hash()].concat()
     }
 
-    fn eip_712_hash(&self) -> [u8; HASH_LEN] {
-        sp1_raw_bytes_keccak256_hash(&self.eip_712_bytes())
+    fn eip_712_hash(&self, domain_separator: &[u8; HASH_LEN]) -> [u8; HASH_LEN] {
+        sp1_raw_bytes_keccak256_hash(&self.eip_712_bytes(domain_separator))
     }
 
-    fn eip_712_signature_hash(&self) -> [u8; HASH_LEN] {
-        let mut eip_712_signature_hash = self.eip_712_hash();
+    fn eip_712_signature_hash(&self, domain_separator: &[u8; HASH_LEN]) -> [u8; HASH_LEN] {
+        let mut eip_712_signature_hash = self.eip_712_hash(domain_separator);
         eip_712_signature_hash.reverse();
         eip_712_signature_hash
     }
@@ -61,10 +69,8 @@ pub trait SP1TxIdHashing: ZK6358DataHashing<ZK6358GoldilocksField> {
 // TransactionInput
 impl SP1EIP712DataHashing for TransactionInput {
     fn type_hash() -> [u8; HASH_LEN] {
-        [
-            119, 84, 107, 54, 3, 160, 139, 205, 137, 18, 192, 2, 17, 139, 145, 206, 79, 30, 17,
-            215, 149, 167, 0, 209, 0, 34, 102, 130, 225, 172, 138, 53,
-        ]
+        static TYPE_HASH: OnceLock<[u8; HASH_LEN]> = OnceLock::new();
+        *TYPE_HASH.get_or_init(|| INPUT_TYPE.type_hash())
     }
 
     fn data_bytes(&self) -> Vec<u8> {
@@ -92,10 +98,8 @@ impl SP1EIP712DataHashing for TransactionInput {
 // TxOutputTarget
 impl SP1EIP712DataHashing for TransactionOutput {
     fn type_hash() -> [u8; HASH_LEN] {
-        [
-            69, 230, 189, 249, 100, 29, 81, 134, 15, 124, 243, 126, 55, 37, 82, 207, 128, 30, 120,
-            162, 115, 136, 26, 187, 55, 232, 245, 171, 182, 105, 236, 166,
-        ]
+        static TYPE_HASH: OnceLock<[u8; HASH_LEN]> = OnceLock::new();
+        *TYPE_HASH.get_or_init(|| OUTPUT_TYPE.type_hash())
     }
 
     fn data_bytes(&self) -> Vec<u8> {
@@ -147,10 +151,8 @@ impl SP1EIP712DataHashing for Vec<TransactionOutput> {
 // deploy
 impl SP1EIP712DataHashing for DeployTransaction {
     fn type_hash() -> [u8; HASH_LEN] {
-        [
-            87, 77, 50, 27, 163, 92, 214, 116, 160, 111, 168, 75, 75, 147, 217, 141, 104, 231, 20,
-            57, 63, 132, 190, 90, 112, 200, 137, 196, 152, 105, 73, 22,
-        ]
+        static TYPE_HASH: OnceLock<[u8; HASH_LEN]> = OnceLock::new();
+        *TYPE_HASH.get_or_init(|| DEPLOY_TYPE.type_hash())
     }
 
     fn data_bytes(&self) -> Vec<u8> {
@@ -200,7 +202,8 @@ impl SP1TxIdHashing for DeployTransaction {
 // mint
 impl SP1EIP712DataHashing for MintTransaction {
     fn type_hash() -> [u8; HASH_LEN] {
-        [65, 248, 17, 248, 4, 254, 170, 184, 160, 96, 46, 26, 213, 54, 185, 120, 54, 63, 142, 160, 150, 182, 62, 166, 175, 18, 168, 44, 71, 254, 251, 202]
+        static TYPE_HASH: OnceLock<[u8; HASH_LEN]> = OnceLock::new();
+        *TYPE_HASH.get_or_init(|| MINT_TYPE.type_hash())
     }
 
     fn data_bytes(&self) -> Vec<u8> {
@@ -240,7 +243,7 @@ pub mod tests {
     use itertools::Itertools;
     use crate::eip::sp1_tx_eip_712::sp1_raw_bytes_keccak256_hash;
 
-    use super::SP1EIP712DataHashing;
+    use super::{SP1EIP712DataHashing, EIP712_DOMAIN_HASH};
     use num::{BigUint, Zero, FromPrimitive};
 
     pub fn p_test_generate_rand_balanced_inputs_outputs(
@@ -322,7 +325,7 @@ pub mod tests {
         let deploy_tx = generate_test_tx_deploy([0; USER_ADDRESS_LEN]);
 
         let eip712_hash = <DeployTransaction as zk_6358::utils6358::tx_eip_712::EIP712DataHashing>::eip_712_signature_hash(&deploy_tx);
-        let sp1_eip712_hash = <DeployTransaction as SP1EIP712DataHashing>::eip_712_signature_hash(&deploy_tx);
+        let sp1_eip712_hash = <DeployTransaction as SP1EIP712DataHashing>::eip_712_signature_hash(&deploy_tx, &EIP712_DOMAIN_HASH);
 
         let p2_keccak = <DeployTransaction as ZK6358DataHashing<ZK6358GoldilocksField>>::hash_keccak256(&deploy_tx);
         let sp1_keccak = sp1_raw_bytes_keccak256_hash(&<DeployTransaction as ZK6358DataHashing<ZK6358GoldilocksField>>::to_bytes(&deploy_tx));
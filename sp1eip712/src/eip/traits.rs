@@ -1,37 +1,219 @@
-use sp1_eip712_type::types::sp1_tx_types::SP1SignedOmniverseTx;
-use zk_6358::utils6358::utxo::HASH_LEN;
+use k256::ecdsa::{RecoveryId, Signature};
+use sp1_eip712_type::types::sp1_tx_types::{SP1SignedOmniverseTx, SP1TxVerdict, SP1_FULL_PK_LEN};
+use sp1_precompiles::secp256k1;
+use zk_6358::utils6358::{type_utils::SIGN_BYTES, utxo::HASH_LEN};
 
 use super::sp1_tx_eip_712::{SP1EIP712DataHashing, SP1TxIdHashing};
 
+/// Whether `sig` can be handed to the `ecrecover` precompile: `r` and `s` in `[1, n)` and a
+/// recovery id of 0 or 1. The precompile may abort the whole proof on anything else instead of
+/// failing the recovery.
+pub fn is_well_formed_signature(sig: &[u8; SIGN_BYTES]) -> bool {
+    Signature::from_slice(&sig[..64]).is_ok()
+        && RecoveryId::from_byte(sig[64]).is_some_and(|recovery_id| !recovery_id.is_x_reduced())
+}
+
 pub trait EIP712ForSignedOmniTx {
-    fn eip_712_hash(&self) -> [u8; HASH_LEN];
-    fn txid_hash(&self) -> [u8; HASH_LEN];
+    fn eip_712_hash(&self, domain_separator: &[u8; HASH_LEN]) -> [u8; HASH_LEN] {
+        self.try_eip_712_hash(domain_separator).expect("invalid transaction")
+    }
+
+    fn txid_hash(&self) -> [u8; HASH_LEN] {
+        self.try_txid_hash().expect("invalid transaction")
+    }
+
+    /// `eip_712_hash`, or `None` for an invalid transaction.
+    fn try_eip_712_hash(&self, domain_separator: &[u8; HASH_LEN]) -> Option<[u8; HASH_LEN]>;
+
+    /// `txid_hash`, or `None` for an invalid transaction.
+    fn try_txid_hash(&self) -> Option<[u8; HASH_LEN]>;
+
+    /// Recovers the signer of the EIP-712 digest and checks it is the owner of the transaction,
+    /// without panicking on any invalid transaction.
+    fn verdict(&self, domain_separator: &[u8; HASH_LEN]) -> SP1TxVerdict {
+        self.recover_signer(domain_separator).0
+    }
+
+    /// The verdict of the transaction and the public key recovered from its signature, if any.
+    fn recover_signer(&self, domain_separator: &[u8; HASH_LEN]) -> (SP1TxVerdict, Option<[u8; SP1_FULL_PK_LEN]>) {
+        self.recover_signer_with(domain_separator, secp256k1::ecrecover)
+    }
+
+    /// `recover_signer` with any secp256k1 public key recovery in place of the precompile, which
+    /// only runs in the zkvm. `ecrecover` is only called on an `is_well_formed_signature`.
+    fn recover_signer_with<R>(&self, domain_separator: &[u8; HASH_LEN], ecrecover: R) -> (SP1TxVerdict, Option<[u8; SP1_FULL_PK_LEN]>)
+    where
+        R: Fn(&[u8; SIGN_BYTES], &[u8; HASH_LEN]) -> Option<[u8; SP1_FULL_PK_LEN]>;
 }
 
 impl EIP712ForSignedOmniTx for SP1SignedOmniverseTx {
-    fn eip_712_hash(&self) -> [u8; HASH_LEN] {
+    fn try_eip_712_hash(&self, domain_separator: &[u8; HASH_LEN]) -> Option<[u8; HASH_LEN]> {
         match self {
             SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) => {
-                signed_deploy_tx.borrow_deploy_tx().eip_712_hash()
-            }
-            SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => signed_mint_tx.borrow_mint_tx().eip_712_hash(),
-            SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => signed_spend_tx.borrow_spend_tx().eip_712_hash(),
-            _ => {
-                panic!("invalid transaction")
+                Some(signed_deploy_tx.borrow_deploy_tx().eip_712_hash(domain_separator))
             }
+            SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => Some(signed_mint_tx.borrow_mint_tx().eip_712_hash(domain_separator)),
+            SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => Some(signed_spend_tx.borrow_spend_tx().eip_712_hash(domain_separator)),
+            SP1SignedOmniverseTx::InvalidTx => None,
         }
     }
 
-    fn txid_hash(&self) -> [u8; HASH_LEN] {
+    fn try_txid_hash(&self) -> Option<[u8; HASH_LEN]> {
         match self {
             SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) => {
-                signed_deploy_tx.borrow_deploy_tx().txid_hashing()
+                Some(signed_deploy_tx.borrow_deploy_tx().txid_hashing())
             }
-            SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => signed_mint_tx.borrow_mint_tx().txid_hashing(),
-            SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => signed_spend_tx.borrow_spend_tx().txid_hashing(),
-            _ => {
-                panic!("invalid transaction")
+            SP1SignedOmniverseTx::OmniMintTx(signed_mint_tx) => Some(signed_mint_tx.borrow_mint_tx().txid_hashing()),
+            SP1SignedOmniverseTx::OmniSpendTx(signed_spend_tx) => Some(signed_spend_tx.borrow_spend_tx().txid_hashing()),
+            SP1SignedOmniverseTx::InvalidTx => None,
+        }
+    }
+
+    fn recover_signer_with<R>(&self, domain_separator: &[u8; HASH_LEN], ecrecover: R) -> (SP1TxVerdict, Option<[u8; SP1_FULL_PK_LEN]>)
+    where
+        R: Fn(&[u8; SIGN_BYTES], &[u8; HASH_LEN]) -> Option<[u8; SP1_FULL_PK_LEN]>,
+    {
+        let (Some(eip712_sign_hash), Some(sig), Some(pk)) = (
+            self.try_eip_712_hash(domain_separator),
+            self.try_get_sig_be(),
+            self.try_full_pk_be(),
+        ) else {
+            return (SP1TxVerdict::InvalidVariant, None);
+        };
+
+        if !is_well_formed_signature(&sig) {
+            return (SP1TxVerdict::UnrecoverableSignature, None);
+        }
+
+        match ecrecover(&sig, &eip712_sign_hash) {
+            None => (SP1TxVerdict::UnrecoverableSignature, None),
+            Some(recovered_pk) if recovered_pk != pk => (SP1TxVerdict::PkMismatch, Some(recovered_pk)),
+            Some(recovered_pk) => (SP1TxVerdict::Valid, Some(recovered_pk)),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////
+/// test
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+    use sp1_eip712_type::types::sp1_tx_types::{SP1SignedOmniverseTx, SP1TxVerdict, SP1_FULL_PK_LEN};
+    use zk_6358::utils6358::{type_utils::SIGN_BYTES, utxo::{HASH_LEN, USER_ADDRESS_LEN}};
+
+    use crate::eip::sp1_tx_eip_712::{tests::generate_test_tx_deploy, SP1EIP712DataHashing, EIP712_DOMAIN_HASH};
+
+    use super::{is_well_formed_signature, EIP712ForSignedOmniTx};
+
+    /// The public key recovery of the precompile, on the host.
+    fn host_ecrecover(sig: &[u8; SIGN_BYTES], msg_hash: &[u8; HASH_LEN]) -> Option<[u8; SP1_FULL_PK_LEN]> {
+        let signature = Signature::from_slice(&sig[..64]).ok()?;
+        let recovery_id = RecoveryId::from_byte(sig[64])?;
+        let pk = VerifyingKey::recover_from_prehash(msg_hash, &signature, recovery_id).ok()?;
+        pk.to_encoded_point(false).as_bytes().try_into().ok()
+    }
+
+    fn full_pk(key: &SigningKey) -> [u8; SP1_FULL_PK_LEN] {
+        VerifyingKey::from(key).to_encoded_point(false).as_bytes().try_into().unwrap()
+    }
+
+    /// A deploy transaction of the owner of `owner_key`, signed in `EIP712_DOMAIN_HASH` with
+    /// `signing_key`.
+    fn signed_deploy_tx(signing_key: &SigningKey, owner_key: &SigningKey) -> SP1SignedOmniverseTx {
+        let pk = full_pk(owner_key);
+        let x: [u8; USER_ADDRESS_LEN] = pk[1..1 + USER_ADDRESS_LEN].try_into().unwrap();
+        let y: [u8; USER_ADDRESS_LEN] = pk[1 + USER_ADDRESS_LEN..].try_into().unwrap();
+
+        let deploy_tx = generate_test_tx_deploy(x);
+        let digest = SP1EIP712DataHashing::eip_712_hash(&deploy_tx, &EIP712_DOMAIN_HASH);
+        let (signature, recovery_id) = signing_key.sign_prehash_recoverable(&digest).unwrap();
+        let mut sig_bytes = [0u8; SIGN_BYTES];
+        sig_bytes[..64].copy_from_slice(&signature.to_bytes());
+        sig_bytes[64] = recovery_id.to_byte();
+
+        SP1SignedOmniverseTx::OmniDeployTx(deploy_tx.sign(&y, &sig_bytes))
+    }
+
+    #[test]
+    fn test_invalid_tx_verdict() {
+        let invalid_tx = SP1SignedOmniverseTx::InvalidTx;
+
+        assert_eq!(invalid_tx.try_txid_hash(), None);
+        assert_eq!(invalid_tx.try_eip_712_hash(&EIP712_DOMAIN_HASH), None);
+        assert_eq!(invalid_tx.verdict(&EIP712_DOMAIN_HASH), SP1TxVerdict::InvalidVariant);
+        assert_eq!(SP1TxVerdict::from_tag(SP1TxVerdict::PkMismatch.tag()), Some(SP1TxVerdict::PkMismatch));
+    }
+
+    #[test]
+    fn test_valid_tx_verdict() {
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let tx = signed_deploy_tx(&key, &key);
+
+        assert_eq!(
+            tx.recover_signer_with(&EIP712_DOMAIN_HASH, host_ecrecover),
+            (SP1TxVerdict::Valid, Some(full_pk(&key)))
+        );
+    }
+
+    #[test]
+    fn test_unrecoverable_signature_verdict() {
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let mut tx = signed_deploy_tx(&key, &key);
+        // zero `r` and `s`, from which no public key can be recovered
+        if let SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) = &mut tx {
+            signed_deploy_tx.signature_le[..64].fill(0);
+        }
+
+        assert_eq!(
+            tx.recover_signer_with(&EIP712_DOMAIN_HASH, host_ecrecover),
+            (SP1TxVerdict::UnrecoverableSignature, None)
+        );
+    }
+
+    #[test]
+    fn test_pk_mismatch_verdict() {
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let other_key = SigningKey::from_slice(&[2u8; 32]).unwrap();
+        // signed with `key` but claiming the public key of `other_key`
+        let tx = signed_deploy_tx(&key, &other_key);
+
+        assert_eq!(
+            tx.recover_signer_with(&EIP712_DOMAIN_HASH, host_ecrecover),
+            (SP1TxVerdict::PkMismatch, Some(full_pk(&key)))
+        );
+    }
+
+    #[test]
+    fn test_malformed_signatures() {
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let tx = signed_deploy_tx(&key, &key);
+        let sig = tx.get_sig_be();
+        assert!(is_well_formed_signature(&sig));
+
+        let mut zero_r = sig;
+        zero_r[..32].fill(0);
+        let mut zero_s = sig;
+        zero_s[32..64].fill(0);
+        // `s` above the group order
+        let mut high_s = sig;
+        high_s[32..64].fill(0xff);
+        let mut x_reduced = sig;
+        x_reduced[64] = 2;
+        let mut bad_recovery_id = sig;
+        bad_recovery_id[64] = 27;
+
+        for malformed in [zero_r, zero_s, high_s, x_reduced, bad_recovery_id] {
+            assert!(!is_well_formed_signature(&malformed));
+
+            let mut tx = tx.clone();
+            if let SP1SignedOmniverseTx::OmniDeployTx(signed_deploy_tx) = &mut tx {
+                signed_deploy_tx.signature_le = malformed;
             }
+            // rejected before the recovery, which the precompile could abort on
+            assert_eq!(
+                tx.recover_signer_with(&EIP712_DOMAIN_HASH, |_, _| panic!("malformed signature reached ecrecover")),
+                (SP1TxVerdict::UnrecoverableSignature, None)
+            );
         }
     }
-}
\ No newline at end of file
+}
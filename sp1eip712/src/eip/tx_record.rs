@@ -0,0 +1,81 @@
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{sol, SolType};
+use sp1_eip712_type::types::sp1_tx_types::{SP1SignedOmniverseTx, SP1_FULL_PK_LEN};
+use zk_6358::utils6358::utxo::HASH_LEN;
+
+use super::sp1_tx_eip_712::sp1_raw_bytes_keccak256_hash;
+use super::traits::EIP712ForSignedOmniTx;
+
+/// `(txid, EIP-712 digest, signer, verdict)` of one transaction.
+pub type TxRecordTuple = sol! {
+    tuple(bytes32, bytes32, address, uint8)
+};
+
+/// The public values of the guest, so Solidity can
+/// `abi.decode(publicValues, (bytes32, bool, bytes32, uint256, (bytes32, bytes32, address, uint8)[]))`
+/// the domain separator, whether invalid transactions were allowed, the txid merkle root, the
+/// count of transactions and the record of every transaction.
+///
+/// With a txid merkle root the records are left out, so the public values do not grow with the
+/// batch; without one the root is zero.
+///
+/// Keep in sync with `fibonacci_script::cooks::eip712::PublicValuesTuple`.
+pub type PublicValuesTuple = sol! {
+    tuple(bytes32, bool, bytes32, uint256, (bytes32, bytes32, address, uint8)[])
+};
+
+/// The Ethereum address of an uncompressed `0x04 || x || y` public key: the last 20 bytes of
+/// `keccak256(x || y)`.
+pub fn eth_address(full_pk_be: &[u8; SP1_FULL_PK_LEN]) -> Address {
+    let pk_hash = sp1_raw_bytes_keccak256_hash(&full_pk_be[1..]);
+    Address::from_slice(&pk_hash[HASH_LEN - 20..])
+}
+
+/// The record of `tx`, with zeros for the txid and digest of an `InvalidTx`, and the zero address
+/// when no signer can be recovered.
+pub fn tx_record(tx: &SP1SignedOmniverseTx, domain_separator: &[u8; HASH_LEN]) -> <TxRecordTuple as SolType>::RustType {
+    let txid = tx.try_txid_hash().unwrap_or([0u8; HASH_LEN]);
+    let digest = tx.try_eip_712_hash(domain_separator).unwrap_or([0u8; HASH_LEN]);
+    let (verdict, recovered_pk) = tx.recover_signer(domain_separator);
+    let signer = recovered_pk.map(|pk| eth_address(&pk)).unwrap_or(Address::ZERO);
+
+    (B256::from(txid), B256::from(digest), signer, verdict.tag())
+}
+
+///////////////////////////////////////////////////////
+/// test
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, B256, U256};
+    use alloy_sol_types::SolType;
+    use sp1_eip712_type::types::sp1_tx_types::{SP1SignedOmniverseTx, SP1TxVerdict};
+
+    use crate::eip::sp1_tx_eip_712::EIP712_DOMAIN_HASH;
+
+    use super::*;
+
+    #[test]
+    fn test_eth_address() {
+        // the public key of the secret key 1 is the generator point
+        let mut full_pk_be = [0u8; SP1_FULL_PK_LEN];
+        full_pk_be[0] = 4;
+        full_pk_be[1..].copy_from_slice(&hex_literal(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        ));
+
+        assert_eq!(eth_address(&full_pk_be), address!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"));
+    }
+
+    #[test]
+    fn test_invalid_tx_record() {
+        let record = tx_record(&SP1SignedOmniverseTx::InvalidTx, &EIP712_DOMAIN_HASH);
+        assert_eq!(record, (B256::ZERO, B256::ZERO, Address::ZERO, SP1TxVerdict::InvalidVariant.tag()));
+
+        let encoded = PublicValuesTuple::abi_encode_params(&(B256::from(EIP712_DOMAIN_HASH), true, B256::ZERO, U256::from(1), vec![record]));
+        assert_eq!(&encoded[..HASH_LEN], &EIP712_DOMAIN_HASH);
+    }
+
+    fn hex_literal(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+}
@@ -0,0 +1,46 @@
+use zk_6358::utils6358::utxo::HASH_LEN;
+
+use super::sp1_tx_eip_712::sp1_raw_bytes_keccak256_hash;
+
+/// The root of the keccak256 merkle tree over `txids`, padded with zero leaves up to the next power
+/// of two, whose inner nodes are `keccak256(left || right)`. The root of no txids is zero.
+///
+/// Keep in sync with `MerkleHash::Keccak256` of `fibonacci_script::utils::merkle::MerkleTree`, which
+/// builds the membership proofs.
+pub fn txid_merkle_root(txids: &[[u8; HASH_LEN]]) -> [u8; HASH_LEN] {
+    if txids.is_empty() {
+        return [0u8; HASH_LEN];
+    }
+
+    let mut layer = txids.to_vec();
+    layer.resize(txids.len().next_power_of_two(), [0u8; HASH_LEN]);
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| sp1_raw_bytes_keccak256_hash(&[pair[0], pair[1]].concat()))
+            .collect();
+    }
+
+    layer[0]
+}
+
+///////////////////////////////////////////////////////
+/// test
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_txid_merkle_root() {
+        let txids = (1..=3u8).map(|i| [i; HASH_LEN]).collect::<Vec<_>>();
+
+        // the vector of `contracts/test/TxidMerkle.t.sol`
+        let root: [u8; HASH_LEN] = [
+            0xb3, 0xea, 0xb8, 0x64, 0x6d, 0x70, 0x62, 0x47, 0xbc, 0x8e, 0x45, 0x4e, 0x88, 0x50, 0xea, 0x39,
+            0xaf, 0x6d, 0xbe, 0x9b, 0xdc, 0x9c, 0x77, 0x51, 0x77, 0x8d, 0xa0, 0xe9, 0x5d, 0x17, 0x7c, 0x34,
+        ];
+        assert_eq!(txid_merkle_root(&txids), root);
+        assert_eq!(txid_merkle_root(&txids[..1]), txids[0]);
+        assert_eq!(txid_merkle_root(&[]), [0u8; HASH_LEN]);
+    }
+}
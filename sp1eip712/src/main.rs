@@ -1,31 +1,53 @@
 #![no_main]
 
-use sp1_eip712_type::types::sp1_tx_types::SP1SignedOmniverseTx;
-use sp1_precompiles::secp256k1;
-use sp1eip712::eip::traits::EIP712ForSignedOmniTx;
+use alloy_primitives::{B256, U256};
+use alloy_sol_types::SolType;
+use sp1_eip712_type::types::{sp1_domain::SP1EIP712DomainInput, sp1_tx_types::{SP1SignedOmniverseTx, SP1TxVerdict}};
+use sp1eip712::eip::{tx_record::{tx_record, PublicValuesTuple}, txid_merkle::txid_merkle_root};
 
 sp1_zkvm::entrypoint!(main);
 
 fn main() {
+    // the domain is hashed here, unless given as a raw separator, and committed first, so the
+    // verifier can check it is its own
+    let domain = sp1_zkvm::io::read::<SP1EIP712DomainInput>();
+    let domain_separator = domain.separator();
+
+    // with verdicts, an invalid transaction is committed as such instead of aborting the proof
+    let with_verdicts = sp1_zkvm::io::read::<bool>();
+    // with a txid root, only the merkle root of the txids and their count are committed
+    let with_txid_root = sp1_zkvm::io::read::<bool>();
+    assert!(!(with_verdicts && with_txid_root), "verdicts are only committed with the records");
+
     let num_cases = sp1_zkvm::io::read::<usize>();
     let omni_signed_txs = (0..num_cases).map(|_| {
         sp1_zkvm::io::read::<SP1SignedOmniverseTx>()
     }).collect::<Vec<_>>();
 
-    for omni_signed_tx in omni_signed_txs {
-        let eip712_sgin_hash = omni_signed_tx.eip_712_hash();
-        // println!("hash inside: {:?}", eip712_sgin_hash);
-        let tx_hash = omni_signed_tx.txid_hash();
-
-        // sp1_zkvm::io::commit(&eip712_sgin_hash);
-        sp1_zkvm::io::commit(&tx_hash);
+    // all the addresses of the input UTXOs are proved to be the same in the `prove_tx_balance` function of the `plonky2 proof`
+    let records = omni_signed_txs
+        .iter()
+        .map(|omni_signed_tx| {
+            let record = tx_record(omni_signed_tx, &domain_separator);
+            if !with_verdicts {
+                assert_eq!(record.3, SP1TxVerdict::Valid.tag(), "Invalid signature");
+            }
+            record
+        })
+        .collect::<Vec<_>>();
 
-        // all the addresses of the input UTXOs are proved to be the same in the `prove_tx_balance` function of the `plonky2 proof`
-        let pk_u8v = omni_signed_tx.full_pk_be();
-        // println!("signature inside: {:?}", omni_signed_tx.get_sig_be());
-        // println!("pk inside: {:?}", pk_u8v);
-        let recovered_pk = secp256k1::ecrecover(&omni_signed_tx.get_sig_be(), &eip712_sgin_hash).unwrap();
+    let (txid_root, records) = if with_txid_root {
+        let txids = records.iter().map(|record| record.0 .0).collect::<Vec<_>>();
+        (B256::from(txid_merkle_root(&txids)), Vec::new())
+    } else {
+        (B256::ZERO, records)
+    };
 
-        assert_eq!(pk_u8v, recovered_pk, "Invalid signature");
-    }
+    sp1_zkvm::io::commit_slice(&PublicValuesTuple::abi_encode_params(&(
+        B256::from(domain_separator),
+        with_verdicts,
+        txid_root,
+        U256::from(num_cases),
+        records,
+    )));
 }